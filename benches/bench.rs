@@ -1,8 +1,9 @@
 use std::time::Duration;
 
+use ark_ff::Field;
 use ark_std::test_rng;
 use ark_std::UniformRand;
-use bls_elgamal::{Fr, G1Affine, SecretKey};
+use bls_elgamal::{pairing, util, Ciphertext, Fr, G1Affine, G2Affine, SecretKey, Uncompressed, G1};
 use criterion::{criterion_group, criterion_main, Criterion};
 
 fn bench_encrypt(c: &mut Criterion) {
@@ -37,9 +38,164 @@ fn bench_decrypt(c: &mut Criterion) {
 
     c.bench_function("bench_decrypt", |bench| {
         bench.iter(|| {
-            std::hint::black_box({
-                let _ = sk.decrypt(ct);
-            });
+            let _ = std::hint::black_box(sk.decrypt(ct));
+        })
+    });
+}
+
+fn bench_decrypt_prepared(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let ct = sk.public_key().encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let prepared = sk.prepare();
+
+    c.bench_function("bench_decrypt_prepared", |bench| {
+        bench.iter(|| {
+            let _ = std::hint::black_box(prepared.decrypt(ct));
+        })
+    });
+}
+
+fn bench_decrypt_try_keys(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let keys = (0..100)
+        .map(|_| SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let ct = keys[0]
+        .public_key()
+        .encrypt(G1Affine::rand(rng), Fr::rand(rng));
+
+    c.bench_function("bench_decrypt_try_100_keys", |bench| {
+        bench.iter(|| std::hint::black_box(SecretKey::decrypt_try_keys(ct, &keys)))
+    });
+
+    c.bench_function("bench_decrypt_100_keys_independently", |bench| {
+        bench.iter(|| {
+            let ms = keys.iter().map(|k| k.decrypt(ct)).collect::<Vec<_>>();
+            std::hint::black_box(ms);
+        })
+    });
+}
+
+fn bench_verify_ciphertext(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let h = G2Affine::rand(rng);
+    let y2 = sk.public_key_g2(h);
+
+    let cts = (0..1000)
+        .map(|_| {
+            let m = G1Affine::rand(rng);
+            (pk.encrypt(m, Fr::rand(rng)), m)
+        })
+        .collect::<Vec<_>>();
+
+    c.bench_function("bench_verify_ciphertext_1000", |bench| {
+        bench.iter(|| {
+            for &(ct, m) in &cts {
+                assert!(pairing::verify_ciphertext(h, y2, ct, m));
+            }
+        })
+    });
+
+    let verifier = pairing::PreparedVerifier::new(h, y2);
+    c.bench_function("bench_prepared_verifier_1000", |bench| {
+        bench.iter(|| {
+            for &(ct, m) in &cts {
+                assert!(verifier.verify(ct, m));
+            }
+        })
+    });
+}
+
+fn bench_batch_invert(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let scalars = (0..1000).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+    c.bench_function("bench_batch_invert_1000", |bench| {
+        bench.iter(|| {
+            let mut scalars = scalars.clone();
+            util::batch_invert(&mut scalars);
+            std::hint::black_box(scalars);
+        })
+    });
+
+    c.bench_function("bench_naive_invert_1000", |bench| {
+        bench.iter(|| {
+            let inverses = scalars
+                .iter()
+                .map(|s| s.inverse().unwrap())
+                .collect::<Vec<_>>();
+            std::hint::black_box(inverses);
+        })
+    });
+}
+
+fn bench_sum_ciphertexts(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    // consecutive sums of a ciphertext, cheaper to set up than 100k encryptions
+    let step = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let cts = std::iter::successors(Some(step), |ct| Some(ct + step))
+        .take(100_000)
+        .collect::<Vec<_>>();
+
+    c.bench_function("bench_sum_100k_copied", |bench| {
+        bench.iter(|| {
+            let sum: Ciphertext<G1> = cts.iter().copied().sum();
+            std::hint::black_box(sum);
+        })
+    });
+
+    c.bench_function("bench_sum_100k_refs", |bench| {
+        bench.iter(|| {
+            let sum: Ciphertext<G1> = cts.iter().map(Ciphertext::view).sum();
+            std::hint::black_box(sum);
+        })
+    });
+
+    c.bench_function("bench_sum_100k_slice", |bench| {
+        bench.iter(|| std::hint::black_box(Ciphertext::sum_slice(&cts)))
+    });
+}
+
+fn bench_deserialize_ciphertexts(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+
+    let step = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let cts = std::iter::successors(Some(step), |ct| Some(ct + step))
+        .take(10_000)
+        .collect::<Vec<_>>();
+    let compressed = cts
+        .iter()
+        .map(|ct| bincode::serialize(ct).unwrap())
+        .collect::<Vec<_>>();
+    let uncompressed = cts
+        .iter()
+        .map(|ct| bincode::serialize(&Uncompressed(*ct)).unwrap())
+        .collect::<Vec<_>>();
+
+    // compressed points need a square root each to recover y, and both forms are
+    // checked to be in the prime order subgroup
+    c.bench_function("bench_deserialize_10k_compressed", |bench| {
+        bench.iter(|| {
+            for bytes in &compressed {
+                let ct: Ciphertext<G1> = bincode::deserialize(bytes).unwrap();
+                std::hint::black_box(ct);
+            }
+        })
+    });
+
+    c.bench_function("bench_deserialize_10k_uncompressed", |bench| {
+        bench.iter(|| {
+            for bytes in &uncompressed {
+                let ct: Ciphertext<G1> = bincode::deserialize(bytes).unwrap();
+                std::hint::black_box(ct);
+            }
         })
     });
 }
@@ -47,7 +203,31 @@ fn bench_decrypt(c: &mut Criterion) {
 criterion_group! {
     name = encrypt_decrypt;
     config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(5));
-    targets = bench_encrypt, bench_decrypt,
+    targets = bench_encrypt, bench_decrypt, bench_decrypt_prepared, bench_decrypt_try_keys,
+}
+
+criterion_group! {
+    name = verify;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(20));
+    targets = bench_verify_ciphertext,
+}
+
+criterion_group! {
+    name = scalars;
+    config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(5));
+    targets = bench_batch_invert,
+}
+
+criterion_group! {
+    name = sums;
+    config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(10));
+    targets = bench_sum_ciphertexts,
+}
+
+criterion_group! {
+    name = serialization;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(20));
+    targets = bench_deserialize_ciphertexts,
 }
 
-criterion_main!(encrypt_decrypt,);
+criterion_main!(encrypt_decrypt, verify, scalars, sums, serialization,);
@@ -0,0 +1,108 @@
+//! A small command line tool exercising the public API end-to-end.
+//!
+//! Keys and ciphertexts are exchanged as hex strings of their bincode (serde)
+//! encoding, and messages as hex strings of compressed G1 points.
+//!
+//! ```text
+//! cargo run --example cli -- keygen
+//! cargo run --example cli -- randmsg
+//! cargo run --example cli -- encrypt --pubkey <hex> --msg-hex <hex>
+//! cargo run --example cli -- decrypt --seckey <hex> --ct <hex>
+//! ```
+
+use std::process::ExitCode;
+
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use bls_elgamal::{Ciphertext, Fr, G1Affine, PublicKey, SecretKey, G1};
+
+const USAGE: &str = "usage:
+    cli keygen
+    cli randmsg
+    cli encrypt --pubkey <hex> --msg-hex <hex>
+    cli decrypt --seckey <hex> --ct <hex>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let rng = &mut rand::thread_rng();
+    match args.first().map(String::as_str) {
+        Some("keygen") => {
+            let sk = SecretKey::new(G1Affine::generator(), Fr::rand(rng));
+            let pk = sk.public_key();
+            println!("seckey: {}", to_hex(&encode(&sk)?));
+            println!("pubkey: {}", to_hex(&encode(&pk)?));
+        }
+        Some("randmsg") => {
+            let mut bytes = Vec::new();
+            G1Affine::rand(rng)
+                .serialize_compressed(&mut bytes)
+                .map_err(|e| e.to_string())?;
+            println!("{}", to_hex(&bytes));
+        }
+        Some("encrypt") => {
+            let pk: PublicKey = decode(&from_hex(flag(args, "--pubkey")?)?)?;
+            let m = G1Affine::deserialize_compressed(&from_hex(flag(args, "--msg-hex")?)?[..])
+                .map_err(|e| format!("invalid message point: {e}"))?;
+            let ct = pk.encrypt(m, Fr::rand(rng));
+            println!("{}", to_hex(&encode(&ct)?));
+        }
+        Some("decrypt") => {
+            let sk: SecretKey = decode(&from_hex(flag(args, "--seckey")?)?)?;
+            let ct: Ciphertext<G1> = decode(&from_hex(flag(args, "--ct")?)?)?;
+            let mut bytes = Vec::new();
+            sk.decrypt(ct)
+                .serialize_compressed(&mut bytes)
+                .map_err(|e| e.to_string())?;
+            println!("{}", to_hex(&bytes));
+        }
+        Some(cmd) => return Err(format!("unknown command `{cmd}`")),
+        None => return Err("missing command".into()),
+    }
+    Ok(())
+}
+
+/// Get the value following the flag `name`.
+fn flag<'a>(args: &'a [String], name: &str) -> Result<&'a str, String> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+        .ok_or_else(|| format!("missing `{name}`"))
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    bincode::serialize(value).map_err(|e| e.to_string())
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    bincode::deserialize(bytes).map_err(|e| e.to_string())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.len().is_multiple_of(2) {
+        return Err("hex string has odd length".into());
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            s.get(i..i + 2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or_else(|| format!("invalid hex at position {i}"))
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+//! Generate known-answer test vectors from a seed, for validating other implementations
+//! against this crate.
+//!
+//! The seed is 32 bytes in hex. As in `bls_elgamal_keygen` of the C API, a ChaCha20 RNG
+//! seeded with it samples every value in turn with `Fr::rand` and `G1Affine::rand`, so the
+//! same seed always gives the same vectors. They are printed as JSON, with all values in
+//! hex:
+//!
+//! - `secret`, `r`: scalars, 32 bytes little-endian.
+//! - `generator`, `public_key`, `message`: compressed G1 points, 48 bytes each.
+//! - `ciphertext`: the compressed points (a, b), 96 bytes, as written by
+//!   `Ciphertext::write_many`.
+//! - `decrypt_proof`: the compressed `SigmaProof` that the ciphertext decrypts to the
+//!   message, 64 bytes.
+//!
+//! ```text
+//! cargo run --example gen_vectors -- <seed hex> [count]
+//! ```
+
+use std::process::ExitCode;
+
+use ark_ec::AffineRepr;
+use ark_serialize::CanonicalSerialize;
+use ark_std::UniformRand;
+use bls_elgamal::{Ciphertext, Fr, G1Affine, SecretKey};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+const USAGE: &str = "usage:
+    gen_vectors <seed hex (32 bytes)> [count (default 4)]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(json) => {
+            println!("{json}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let seed_hex = args.first().ok_or("missing seed")?;
+    let seed: [u8; 32] = from_hex(seed_hex)?
+        .try_into()
+        .map_err(|_| "seed must be 32 bytes".to_string())?;
+    let count = match args.get(1) {
+        Some(count) => count
+            .parse::<usize>()
+            .map_err(|e| format!("invalid count: {e}"))?,
+        None => 4,
+    };
+
+    let rng = &mut ChaCha20Rng::from_seed(seed);
+    let vectors = (0..count)
+        .map(|_| {
+            let x = Fr::rand(rng);
+            let sk = SecretKey::new(G1Affine::generator(), x);
+            let pk = sk.public_key();
+            let m = G1Affine::rand(rng);
+            let r = Fr::rand(rng);
+            let ct = pk.encrypt(m, r);
+            let proof = sk.prove_decrypt_sigma(&ct, rng);
+
+            let mut ct_bytes = Vec::new();
+            Ciphertext::write_many(&mut ct_bytes, &[ct]).map_err(|e| e.to_string())?;
+            let fields = [
+                ("secret", compressed(&x)?),
+                ("generator", compressed(&pk.generator())?),
+                ("public_key", compressed(&pk.y())?),
+                ("message", compressed(&m)?),
+                ("r", compressed(&r)?),
+                ("ciphertext", ct_bytes),
+                ("decrypt_proof", compressed(&proof)?),
+            ];
+            let fields = fields
+                .iter()
+                .map(|(name, bytes)| format!("      \"{name}\": \"{}\"", to_hex(bytes)))
+                .collect::<Vec<_>>();
+            Ok(format!("    {{\n{}\n    }}", fields.join(",\n")))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(format!(
+        "{{\n  \"seed\": \"{}\",\n  \"vectors\": [\n{}\n  ]\n}}",
+        to_hex(&seed),
+        vectors.join(",\n")
+    ))
+}
+
+fn compressed<T: CanonicalSerialize>(value: &T) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.len().is_multiple_of(2) {
+        return Err("hex string has odd length".into());
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            s.get(i..i + 2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or_else(|| format!("invalid hex at position {i}"))
+        })
+        .collect()
+}
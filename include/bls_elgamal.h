@@ -0,0 +1,43 @@
+/* C API of the bls-elgamal crate, built with the `capi` feature. */
+#ifndef BLS_ELGAMAL_H
+#define BLS_ELGAMAL_H
+
+#include <stdint.h>
+
+#define BLS_ELGAMAL_SEED_SIZE 32
+#define BLS_ELGAMAL_SCALAR_SIZE 32
+#define BLS_ELGAMAL_MESSAGE_SIZE 48
+#define BLS_ELGAMAL_PUBLIC_KEY_SIZE 96
+#define BLS_ELGAMAL_SECRET_KEY_SIZE 128
+#define BLS_ELGAMAL_CIPHERTEXT_SIZE 96
+
+#define BLS_ELGAMAL_OK 0
+#define BLS_ELGAMAL_ERR_NULL_POINTER -1
+#define BLS_ELGAMAL_ERR_OUT_OF_RANGE 1
+#define BLS_ELGAMAL_ERR_TABLE_TOO_LARGE 2
+#define BLS_ELGAMAL_ERR_GENERATOR_MISMATCH 3
+#define BLS_ELGAMAL_ERR_INVALID_ENCODING 4
+#define BLS_ELGAMAL_ERR_INVALID_GENERATOR 5
+#define BLS_ELGAMAL_ERR_LENGTH_MISMATCH 6
+#define BLS_ELGAMAL_ERR_UNEXPECTED_CURVE 7
+#define BLS_ELGAMAL_ERR_EMPTY_INPUT 8
+#define BLS_ELGAMAL_ERR_INVALID_RANDOMNESS 9
+#define BLS_ELGAMAL_ERR_INVALID_LENGTH 10
+#define BLS_ELGAMAL_ERR_UNSUPPORTED_VERSION 11
+#define BLS_ELGAMAL_ERR_CORRUPT_KEY 12
+#define BLS_ELGAMAL_ERR_BUFFER_TOO_SMALL 13
+#define BLS_ELGAMAL_ERR_AUTHENTICATION_FAILED 14
+#define BLS_ELGAMAL_ERR_REUSED_RANDOMNESS 15
+#define BLS_ELGAMAL_ERR_DEGENERATE_KEY 16
+
+/* Derive a key pair from a 32-byte seed. */
+int32_t bls_elgamal_keygen(const uint8_t *seed_ptr, uint8_t *out_sk, uint8_t *out_pk);
+
+/* Encrypt a compressed G1 point with a public key and a little-endian scalar. */
+int32_t bls_elgamal_encrypt(const uint8_t *pk_ptr, const uint8_t *msg_ptr, const uint8_t *r_ptr,
+                            uint8_t *out_ct);
+
+/* Decrypt a ciphertext with a secret key into a compressed G1 point. */
+int32_t bls_elgamal_decrypt(const uint8_t *sk_ptr, const uint8_t *ct_ptr, uint8_t *out_msg);
+
+#endif /* BLS_ELGAMAL_H */
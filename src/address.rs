@@ -0,0 +1,40 @@
+//! Bech32 encoding of public keys as single-line identifiers, e.g. `bpk1...`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bech32::{primitives::decode::CheckedHrpstring, Bech32, Hrp};
+
+use super::{Error, PublicKey};
+
+/// The human-readable prefix of the bech32 encoding of a public key.
+const PUBLIC_KEY_HRP: &str = "bpk";
+
+fn public_key_hrp() -> Hrp {
+    Hrp::parse(PUBLIC_KEY_HRP).expect("the prefix is a valid human-readable part")
+}
+
+impl fmt::Display for PublicKey {
+    /// Format the key as a lowercase bech32 string with the prefix `bpk`, whose data is
+    /// the compressed generator || y of [`PublicKey::to_bytes`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        bech32::encode_to_fmt::<Bech32, _>(f, public_key_hrp(), &self.to_bytes())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = Error;
+
+    /// Parse a key formatted by the `Display` impl. Returns [`Error::InvalidEncoding`] if
+    /// the string is not bech32, the checksum is wrong or the prefix is not `bpk`, or the
+    /// errors of `PublicKey::try_from` for the data.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let checked = CheckedHrpstring::new::<Bech32>(s).map_err(|_| Error::InvalidEncoding)?;
+        if checked.hrp() != public_key_hrp() {
+            return Err(Error::InvalidEncoding);
+        }
+        let bytes: Vec<u8> = checked.byte_iter().collect();
+        PublicKey::try_from(bytes.as_slice())
+    }
+}
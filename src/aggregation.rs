@@ -0,0 +1,148 @@
+//! Merkle sum trees over ciphertexts, committing to a list of input ciphertexts and their
+//! homomorphic sum at once.
+//!
+//! Every node of the tree holds a hash and the sum of the ciphertexts below it, and the
+//! hash of an inner node covers the hashes of its children and its own sum. An
+//! aggregator publishes the root hash and the sum, and gives the holder of each input an
+//! [`AggProof`]: the siblings on the path from the input to the root. Verifying the proof
+//! checks that the input is committed under the root and recomputes the sums along the
+//! path, so every holder checks that its input is counted in the published sum, without
+//! seeing the other inputs.
+
+use ark_ec::CurveGroup;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+use super::{ciphertext::Ciphertext, error::Error};
+
+/// The domain separation prefix of the hash of a leaf.
+const LEAF_PREFIX: u8 = 0;
+/// The domain separation prefix of the hash of an inner node.
+const NODE_PREFIX: u8 = 1;
+
+/// A Merkle sum tree over a list of ciphertexts, see the [module](self) documentation.
+///
+/// A level with an odd number of nodes promotes its last node to the next level as it
+/// is, so no input is counted twice.
+#[derive(Clone, Debug)]
+pub struct AggregationTree<G: CurveGroup> {
+    /// The levels of (hash, sum) nodes, from the leaves to the root.
+    levels: Vec<Vec<([u8; 32], Ciphertext<G>)>>,
+}
+
+/// A proof that a ciphertext is a leaf of an [`AggregationTree`] and is counted in the
+/// sum at its root, created by [`AggregationTree::prove`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AggProof<G: CurveGroup> {
+    /// The index of the leaf.
+    index: usize,
+    /// The sibling (hash, sum) on each level from the leaf up, or `None` if the node is
+    /// promoted on that level.
+    siblings: Vec<Option<([u8; 32], Ciphertext<G>)>>,
+}
+
+impl<G: CurveGroup> AggregationTree<G> {
+    /// Build the tree over the ciphertexts `inputs`, in order. Returns
+    /// [`Error::EmptyInput`] if there are none.
+    pub fn new(inputs: &[Ciphertext<G>]) -> Result<Self, Error> {
+        if inputs.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+        let mut levels = vec![inputs
+            .iter()
+            .map(|ct| (leaf_hash(ct), *ct))
+            .collect::<Vec<_>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks have one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Ok(Self { levels })
+    }
+
+    /// Get the root hash, which commits to the inputs and all the sums of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0].0
+    }
+
+    /// Get the homomorphic sum of the inputs.
+    pub fn sum(&self) -> Ciphertext<G> {
+        self.levels[self.levels.len() - 1][0].1
+    }
+
+    /// Prove that the input at `index` is counted in the sum, or return `None` if there
+    /// is no such input.
+    pub fn prove(&self, index: usize) -> Option<AggProof<G>> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+        let siblings = self.levels[..self.levels.len() - 1]
+            .iter()
+            .scan(index, |i, level| {
+                let sibling = level.get(*i ^ 1).copied();
+                *i /= 2;
+                Some(sibling)
+            })
+            .collect();
+        Some(AggProof { index, siblings })
+    }
+}
+
+impl<G: CurveGroup> AggProof<G> {
+    /// Get the index of the input the proof is for.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Check that the ciphertext `input` is committed under the root hash `root` at the
+    /// index of the proof, and that the sums along its path add up to `sum`.
+    pub fn verify(&self, root: &[u8; 32], sum: &Ciphertext<G>, input: &Ciphertext<G>) -> bool {
+        let mut current = (leaf_hash(input), *input);
+        let mut i = self.index;
+        for sibling in &self.siblings {
+            if let Some(sibling) = sibling {
+                current = if i.is_multiple_of(2) {
+                    node(&current, sibling)
+                } else {
+                    node(sibling, &current)
+                };
+            }
+            i /= 2;
+        }
+        i == 0 && current.0 == *root && current.1 == *sum
+    }
+}
+
+fn leaf_hash<G: CurveGroup>(ct: &Ciphertext<G>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(ciphertext_bytes(ct));
+    hasher.finalize().into()
+}
+
+fn node<G: CurveGroup>(
+    left: &([u8; 32], Ciphertext<G>),
+    right: &([u8; 32], Ciphertext<G>),
+) -> ([u8; 32], Ciphertext<G>) {
+    let sum = left.1 + right.1;
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left.0);
+    hasher.update(right.0);
+    hasher.update(ciphertext_bytes(&sum));
+    (hasher.finalize().into(), sum)
+}
+
+fn ciphertext_bytes<G: CurveGroup>(ct: &Ciphertext<G>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    G::normalize_batch(&[ct.0, ct.1])
+        .serialize_compressed(&mut bytes)
+        .expect("serializing to a vector cannot fail");
+    bytes
+}
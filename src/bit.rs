@@ -0,0 +1,95 @@
+//! Encrypted bits with a proof that the plaintext is 0 or 1, e.g. for yes/no ballots.
+//!
+//! A bit b is encrypted like [`EncryptKey::encrypt_scalar`], i.e. as the message bP on
+//! the standard generator P, so bits add up homomorphically to an encrypted count (the
+//! Hamming weight) that decrypts with a [`BsgsTable`](crate::BsgsTable).
+
+use std::ops::Add;
+
+use ark_ec::CurveGroup;
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use sha2::{Digest, Sha256};
+
+use super::{
+    bsgs::encode_scalar, ciphertext::Ciphertext, encrypt::EncryptKey, proof::OrProof, PublicKey, G1,
+};
+
+/// A ciphertext of a bit with an [`OrProof`] that it encrypts 0 or 1, created by
+/// [`EncryptKey::encrypt_bit`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedBit<G: CurveGroup> {
+    ct: Ciphertext<G>,
+    proof: OrProof<G>,
+}
+
+impl<G: CurveGroup> EncryptedBit<G> {
+    /// Create an encrypted bit from its ciphertext `ct` and proof `proof`, e.g. as
+    /// received over the wire. Nothing is checked; verify the bit with
+    /// [`EncryptKey::verify_bit`] before using it.
+    pub fn new(ct: Ciphertext<G>, proof: OrProof<G>) -> Self {
+        Self { ct, proof }
+    }
+
+    /// Get the ciphertext of the bit.
+    pub fn ciphertext(&self) -> &Ciphertext<G> {
+        &self.ct
+    }
+
+    /// Get the proof that the ciphertext encrypts 0 or 1.
+    pub fn proof(&self) -> &OrProof<G> {
+        &self.proof
+    }
+}
+
+impl<G: CurveGroup> EncryptKey<G> {
+    /// Encrypt the bit `b` with fresh randomness and prove that the plaintext is 0 or 1.
+    pub fn encrypt_bit<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        b: bool,
+        rng: &mut R,
+    ) -> EncryptedBit<G> {
+        let r = <G::ScalarField as UniformRand>::rand(rng);
+        let ct = self.encrypt_scalar(b as u64, r);
+        let proof = self.prove_encryption_of_one_of::<D, _>(&ct, r, b, bit_candidates::<G>(), rng);
+        EncryptedBit { ct, proof }
+    }
+
+    /// Verify that the encrypted bit `bit` encrypts 0 or 1 under this key.
+    pub fn verify_bit<D: Digest>(&self, bit: &EncryptedBit<G>) -> bool {
+        self.verify_encryption_of_one_of::<D>(&bit.ct, bit_candidates::<G>(), &bit.proof)
+    }
+}
+
+/// The messages of the bits 0 and 1.
+fn bit_candidates<G: CurveGroup>() -> [G::Affine; 2] {
+    [encode_scalar::<G>(0), encode_scalar::<G>(1)]
+}
+
+impl<G: CurveGroup> Add for EncryptedBit<G> {
+    type Output = Ciphertext<G>;
+
+    /// Add two bits to get a ciphertext of their sum, a small integer.
+    fn add(self, other: Self) -> Ciphertext<G> {
+        self.ct + other.ct
+    }
+}
+
+impl<G: CurveGroup> Add<EncryptedBit<G>> for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    /// Add a bit to a ciphertext of a small integer, e.g. a running count of bits.
+    fn add(self, bit: EncryptedBit<G>) -> Ciphertext<G> {
+        self + bit.ct
+    }
+}
+
+impl EncryptedBit<G1> {
+    /// Verify that the bit encrypts 0 or 1 under the key `pk`, with a proof from
+    /// [`PublicKey::encrypt_bit`].
+    pub fn verify(&self, pk: &PublicKey) -> bool {
+        pk.inner.verify_bit::<Sha256>(self)
+    }
+}
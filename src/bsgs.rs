@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use ark_ec::{CurveGroup, PrimeGroup};
+
+use super::{
+    error::Error,
+    util::{fr_from_i64, fr_from_u64},
+};
+
+/// The maximum number of giant steps a table may need to cover its range. A failed
+/// lookup on BLS12-381 G1 takes about 1.5 microseconds per giant step, so at this cap
+/// it takes around 25 seconds; larger ranges are rejected instead.
+pub const MAX_GIANT_STEPS: u64 = 1 << 24;
+
+/// The number of giant steps normalized together per lookup batch.
+const GIANT_BATCH: u64 = 256;
+
+/// A precomputed baby-step giant-step table to recover a small integer `v` in `[0, max]`
+/// from the point `vG`, where `G` is the standard generator of the group (see
+/// [`EncryptKey::encrypt_scalar`](crate::encrypt::EncryptKey::encrypt_scalar)).
+///
+/// The range `max + 1` is split into `m` baby steps (stored in the table) and
+/// `ceil((max + 1) / m)` giant steps (computed per lookup). Memory grows linearly with
+/// `m` while the worst-case lookup time grows with the number of giant steps, so the
+/// split is balanced at `m = sqrt(max + 1)` when the memory budget allows, and otherwise
+/// `m` is made as large as the budget permits, trading lookup time for memory.
+///
+/// A failed lookup walks all giant steps, so its worst-case time is about
+/// 1.5 microseconds per [giant step](BsgsTable::giant_steps) on BLS12-381 G1: around
+/// 1.5 milliseconds for the balanced split of `max = 2^20`, 0.1 seconds for the balanced
+/// split of `max = 2^32`, and around 25 seconds for a table that reaches
+/// [`MAX_GIANT_STEPS`].
+pub struct BsgsTable<G: CurveGroup> {
+    /// The baby steps iG for i in [0, m).
+    baby_steps: HashMap<G::Affine, u64>,
+    /// The giant step -mG.
+    giant_step: G,
+    /// The number of giant steps needed to cover the range.
+    giant_count: u64,
+    max: u64,
+}
+
+impl<G: CurveGroup> BsgsTable<G> {
+    /// Create a table for recovering values in `[0, max]`, using at most (approximately)
+    /// `mem_budget_bytes` bytes of memory for the baby steps.
+    ///
+    /// Returns [`Error::TableTooLarge`] if the budget cannot hold a single entry, or if
+    /// covering `max` would need more than [`MAX_GIANT_STEPS`] giant steps.
+    pub fn new(max: u64, mem_budget_bytes: usize) -> Result<Self, Error> {
+        let range = max as u128 + 1;
+        let balanced = ceil_sqrt(range);
+        let baby_count = balanced.min(max_entries::<G>(mem_budget_bytes) as u128);
+        if baby_count == 0 {
+            return Err(Error::TableTooLarge);
+        }
+        let giant_count = range.div_ceil(baby_count);
+        if giant_count > MAX_GIANT_STEPS as u128 {
+            return Err(Error::TableTooLarge);
+        }
+        let (baby_count, giant_count) = (baby_count as u64, giant_count as u64);
+
+        let generator = G::generator();
+        let mut points = Vec::with_capacity(baby_count as usize);
+        let mut acc = G::zero();
+        for _ in 0..baby_count {
+            points.push(acc);
+            acc += generator;
+        }
+        count_ops!(NORMALIZATIONS);
+        let baby_steps = G::normalize_batch(&points)
+            .into_iter()
+            .zip(0..)
+            .collect::<HashMap<_, _>>();
+
+        Ok(Self {
+            baby_steps,
+            giant_step: -acc,
+            giant_count,
+            max,
+        })
+    }
+
+    /// Find `v` in `[0, max]` such that `point = vG`, or return [`Error::OutOfRange`].
+    pub fn discrete_log(&self, point: G) -> Result<u64, Error> {
+        let baby_count = self.baby_steps.len() as u64;
+        let mut current = point;
+        let mut batch = Vec::with_capacity(GIANT_BATCH.min(self.giant_count) as usize);
+        for start in (0..self.giant_count).step_by(GIANT_BATCH as usize) {
+            // normalize the giant steps in batches, as a single inversion per batch is
+            // much cheaper than one per step
+            batch.clear();
+            for _ in start..(start + GIANT_BATCH).min(self.giant_count) {
+                batch.push(current);
+                current += self.giant_step;
+            }
+            count_ops!(NORMALIZATIONS);
+            for (j, affine) in (start..).zip(G::normalize_batch(&batch)) {
+                if let Some(i) = self.baby_steps.get(&affine) {
+                    let v = j * baby_count + i;
+                    return if v <= self.max {
+                        Ok(v)
+                    } else {
+                        Err(Error::OutOfRange)
+                    };
+                }
+            }
+        }
+        Err(Error::OutOfRange)
+    }
+
+    /// Find `v` in `[-max, max]` such that `point = vG`, where negative values are
+    /// encoded as their field negation (see
+    /// [`EncryptKey::encrypt_signed`](crate::encrypt::EncryptKey::encrypt_signed)), or
+    /// return [`Error::OutOfRange`].
+    ///
+    /// The non-negative range is searched first and then the negative one, so a lookup
+    /// of a negative value (or a failed lookup) takes up to twice as long as
+    /// [`BsgsTable::discrete_log`]. Values beyond the range of `i64` are out of range.
+    pub fn discrete_log_signed(&self, point: G) -> Result<i64, Error> {
+        match self.discrete_log(point) {
+            Ok(v) => i64::try_from(v).map_err(|_| Error::OutOfRange),
+            Err(_) => {
+                let v = self.discrete_log(-point)?;
+                i64::try_from(-(v as i128)).map_err(|_| Error::OutOfRange)
+            }
+        }
+    }
+
+    /// Get the maximum value that can be recovered.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Get the number of baby steps stored in the table.
+    pub fn baby_steps(&self) -> u64 {
+        self.baby_steps.len() as u64
+    }
+
+    /// Get the number of giant steps a lookup takes in the worst case.
+    pub fn giant_steps(&self) -> u64 {
+        self.giant_count
+    }
+}
+
+/// The largest sum of `count` values in `[0, per_value_max]`, saturating at `u64::MAX`.
+/// A table with [`BsgsTable::max`] at least this large can recover the homomorphic sum
+/// of `count` such values.
+pub fn bsgs_max_sum(per_value_max: u64, count: usize) -> u64 {
+    per_value_max.saturating_mul(count as u64)
+}
+
+/// The largest number of entries whose hash table fits in `budget` bytes.
+///
+/// The estimate follows the layout of the standard hash map: each bucket stores a key,
+/// a value and one control byte, buckets are allocated in powers of two, and at most
+/// 7/8 of them are used.
+fn max_entries<G: CurveGroup>(budget: usize) -> u64 {
+    let bucket_size = std::mem::size_of::<(G::Affine, u64)>() + 1;
+    let max_buckets = budget / bucket_size;
+    if max_buckets < 8 {
+        // small tables use all of their (at least four) buckets
+        return if max_buckets >= 4 { 3 } else { 0 };
+    }
+    let buckets = 1u64 << (usize::BITS - 1 - max_buckets.leading_zeros());
+    buckets / 8 * 7
+}
+
+/// The smallest `s` such that `s * s >= n`.
+fn ceil_sqrt(n: u128) -> u128 {
+    let mut s = (n as f64).sqrt() as u128;
+    while s * s < n {
+        s += 1;
+    }
+    while s > 0 && (s - 1) * (s - 1) >= n {
+        s -= 1;
+    }
+    s
+}
+
+/// Encode a small integer `v` as the point `vG` on the standard generator `G`.
+pub(crate) fn encode_scalar<G: CurveGroup>(v: u64) -> G::Affine {
+    count_ops!(SCALAR_MULS);
+    count_ops!(NORMALIZATIONS);
+    (G::generator() * fr_from_u64::<<G as PrimeGroup>::ScalarField>(v)).into_affine()
+}
+
+/// Encode a small signed integer `v` as the point `vG` on the standard generator `G`,
+/// i.e. a negative `v` is the point (p - |v|)G where `p` is the group order.
+pub(crate) fn encode_signed<G: CurveGroup>(v: i64) -> G::Affine {
+    count_ops!(SCALAR_MULS);
+    count_ops!(NORMALIZATIONS);
+    (G::generator() * fr_from_i64::<<G as PrimeGroup>::ScalarField>(v)).into_affine()
+}
@@ -0,0 +1,169 @@
+//! Strict fixed-width encodings of the BLS12-381 wrappers and ciphertexts, as
+//! concatenations of compressed points and little-endian scalars with no framing. The
+//! layouts are the same as those of the C API.
+
+use std::cmp::Ordering;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use super::{Ciphertext, DecryptKey, EncryptKey, Error, Fr, G1Affine, PublicKey, SecretKey, G1};
+
+/// The size of a compressed G1 point.
+const POINT_SIZE: usize = 48;
+/// The size of a scalar.
+const SCALAR_SIZE: usize = 32;
+
+impl Ciphertext<G1> {
+    /// The size of [`Ciphertext::to_bytes`]: the compressed points a || b.
+    pub const SERIALIZED_SIZE: usize = 2 * POINT_SIZE;
+
+    /// Serialize the ciphertext as its compressed points a || b, parsed back by
+    /// `Ciphertext::try_from`.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0u8; Self::SERIALIZED_SIZE];
+        write(&mut bytes[..POINT_SIZE], &G1Affine::from(self.0));
+        write(&mut bytes[POINT_SIZE..], &G1Affine::from(self.1));
+        bytes
+    }
+}
+
+impl PublicKey {
+    /// The size of [`PublicKey::to_bytes`]: the compressed generator || y.
+    pub const SERIALIZED_SIZE: usize = 2 * POINT_SIZE;
+
+    /// Serialize the key as its compressed generator || y, parsed back by
+    /// `PublicKey::try_from`.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0u8; Self::SERIALIZED_SIZE];
+        write(&mut bytes[..POINT_SIZE], &self.generator());
+        write(&mut bytes[POINT_SIZE..], &self.y());
+        bytes
+    }
+}
+
+impl SecretKey {
+    /// The size of [`SecretKey::to_bytes`]: the secret || the compressed generator || y.
+    pub const SERIALIZED_SIZE: usize = SCALAR_SIZE + PublicKey::SERIALIZED_SIZE;
+
+    /// Serialize the key as its secret followed by its public key, parsed back by
+    /// `SecretKey::try_from`. See [`SecretKey::to_bytes_compact`] for a shorter encoding.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0u8; Self::SERIALIZED_SIZE];
+        write(&mut bytes[..SCALAR_SIZE], &self.inner.secret());
+        bytes[SCALAR_SIZE..].copy_from_slice(&self.public_key().to_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for Ciphertext<G1> {
+    type Error = Error;
+
+    /// Parse a ciphertext of exactly [`Ciphertext::SERIALIZED_SIZE`] bytes. Returns
+    /// [`Error::InvalidLength`] for any other length, or [`Error::InvalidEncoding`] if a
+    /// point is invalid.
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        check_length(bytes, Self::SERIALIZED_SIZE)?;
+        let a: G1Affine = read(&bytes[..POINT_SIZE])?;
+        let b: G1Affine = read(&bytes[POINT_SIZE..])?;
+        Ok(Ciphertext(a.into(), b.into()))
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = Error;
+
+    /// Parse a key of exactly [`PublicKey::SERIALIZED_SIZE`] bytes. Returns
+    /// [`Error::InvalidLength`] for any other length, [`Error::InvalidEncoding`] if a
+    /// point is invalid, or [`Error::InvalidGenerator`] if the generator is the identity.
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        check_length(bytes, Self::SERIALIZED_SIZE)?;
+        Ok(PublicKey {
+            inner: EncryptKey::from_compressed(&bytes[..POINT_SIZE], &bytes[POINT_SIZE..])?,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for SecretKey {
+    type Error = Error;
+
+    /// Parse a key of exactly [`SecretKey::SERIALIZED_SIZE`] bytes. Returns
+    /// [`Error::InvalidLength`] for any other length, [`Error::InvalidGenerator`] if the
+    /// generator is the identity, [`Error::InvalidEncoding`] if a value is invalid, or
+    /// [`Error::CorruptKey`] if the public key does not match the secret.
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        check_length(bytes, Self::SERIALIZED_SIZE)?;
+        let x: Fr = read(&bytes[..SCALAR_SIZE])?;
+        let pk = PublicKey::try_from(&bytes[SCALAR_SIZE..])?;
+        let sk = SecretKey {
+            inner: DecryptKey {
+                secret: x,
+                encrypt_key: pk.inner,
+            },
+        };
+        if !sk.validate() {
+            return Err(Error::CorruptKey);
+        }
+        Ok(sk)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Ciphertext<G1> {
+    type Error = Error;
+
+    /// See the impl of `TryFrom<&[u8]>`.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<Vec<u8>> for PublicKey {
+    type Error = Error;
+
+    /// See the impl of `TryFrom<&[u8]>`.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<Vec<u8>> for SecretKey {
+    type Error = Error;
+
+    /// See the impl of `TryFrom<&[u8]>`.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl Ord for PublicKey {
+    /// Order keys lexicographically by [`PublicKey::to_bytes`], i.e. by generator and
+    /// then by y, e.g. to sort a set of keys canonically before hashing it.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(&other.to_bytes())
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn check_length(bytes: &[u8], expected: usize) -> Result<(), Error> {
+    if bytes.len() != expected {
+        return Err(Error::InvalidLength {
+            expected,
+            got: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+fn read<T: CanonicalDeserialize>(bytes: &[u8]) -> Result<T, Error> {
+    T::deserialize_compressed(bytes).map_err(|_| Error::InvalidEncoding)
+}
+
+fn write<T: CanonicalSerialize>(bytes: &mut [u8], value: &T) {
+    value
+        .serialize_compressed(bytes)
+        .expect("the buffer has the size of the value");
+}
@@ -0,0 +1,235 @@
+//! A C API over the BLS12-381 wrappers (enabled by the `capi` feature).
+//!
+//! All keys, messages and ciphertexts are passed as fixed-width buffers of compressed
+//! points and little-endian scalars:
+//!
+//! | Buffer      | Layout                                   | Size                             |
+//! |-------------|------------------------------------------|----------------------------------|
+//! | seed        | 32 random bytes                          | [`BLS_ELGAMAL_SEED_SIZE`]        |
+//! | scalar `r`  | canonical little-endian scalar           | [`BLS_ELGAMAL_SCALAR_SIZE`]      |
+//! | message     | compressed G1 point                      | [`BLS_ELGAMAL_MESSAGE_SIZE`]     |
+//! | public key  | generator \|\| y                         | [`BLS_ELGAMAL_PUBLIC_KEY_SIZE`]  |
+//! | secret key  | secret \|\| generator \|\| y             | [`BLS_ELGAMAL_SECRET_KEY_SIZE`]  |
+//! | ciphertext  | a \|\| b                                 | [`BLS_ELGAMAL_CIPHERTEXT_SIZE`]  |
+//!
+//! Every function returns [`BLS_ELGAMAL_OK`] on success, or an error code otherwise, in
+//! which case the output buffers are left untouched. The declarations are in
+//! `include/bls_elgamal.h`. To build a library for C, run e.g.
+//!
+//! ```sh
+//! cargo rustc --release --features capi --crate-type cdylib
+//! ```
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+use super::{Ciphertext, Error, Fr, G1Affine, PublicKey, SecretKey};
+
+pub const BLS_ELGAMAL_SEED_SIZE: usize = 32;
+pub const BLS_ELGAMAL_SCALAR_SIZE: usize = 32;
+pub const BLS_ELGAMAL_MESSAGE_SIZE: usize = 48;
+pub const BLS_ELGAMAL_PUBLIC_KEY_SIZE: usize = 2 * BLS_ELGAMAL_MESSAGE_SIZE;
+pub const BLS_ELGAMAL_SECRET_KEY_SIZE: usize =
+    BLS_ELGAMAL_SCALAR_SIZE + BLS_ELGAMAL_PUBLIC_KEY_SIZE;
+pub const BLS_ELGAMAL_CIPHERTEXT_SIZE: usize = 2 * BLS_ELGAMAL_MESSAGE_SIZE;
+
+/// The operation succeeded.
+pub const BLS_ELGAMAL_OK: i32 = 0;
+/// A pointer argument is null.
+pub const BLS_ELGAMAL_ERR_NULL_POINTER: i32 = -1;
+/// See [`Error::OutOfRange`].
+pub const BLS_ELGAMAL_ERR_OUT_OF_RANGE: i32 = 1;
+/// See [`Error::TableTooLarge`].
+pub const BLS_ELGAMAL_ERR_TABLE_TOO_LARGE: i32 = 2;
+/// See [`Error::GeneratorMismatch`].
+pub const BLS_ELGAMAL_ERR_GENERATOR_MISMATCH: i32 = 3;
+/// See [`Error::InvalidEncoding`].
+pub const BLS_ELGAMAL_ERR_INVALID_ENCODING: i32 = 4;
+/// See [`Error::InvalidGenerator`].
+pub const BLS_ELGAMAL_ERR_INVALID_GENERATOR: i32 = 5;
+/// See [`Error::LengthMismatch`].
+pub const BLS_ELGAMAL_ERR_LENGTH_MISMATCH: i32 = 6;
+/// See [`Error::UnexpectedCurve`].
+pub const BLS_ELGAMAL_ERR_UNEXPECTED_CURVE: i32 = 7;
+/// See [`Error::EmptyInput`].
+pub const BLS_ELGAMAL_ERR_EMPTY_INPUT: i32 = 8;
+/// See [`Error::InvalidRandomness`].
+pub const BLS_ELGAMAL_ERR_INVALID_RANDOMNESS: i32 = 9;
+/// See [`Error::InvalidLength`].
+pub const BLS_ELGAMAL_ERR_INVALID_LENGTH: i32 = 10;
+/// See [`Error::UnsupportedVersion`].
+pub const BLS_ELGAMAL_ERR_UNSUPPORTED_VERSION: i32 = 11;
+/// See [`Error::CorruptKey`].
+pub const BLS_ELGAMAL_ERR_CORRUPT_KEY: i32 = 12;
+/// See [`Error::BufferTooSmall`].
+pub const BLS_ELGAMAL_ERR_BUFFER_TOO_SMALL: i32 = 13;
+/// See [`Error::AuthenticationFailed`].
+pub const BLS_ELGAMAL_ERR_AUTHENTICATION_FAILED: i32 = 14;
+/// See [`Error::ReusedRandomness`].
+pub const BLS_ELGAMAL_ERR_REUSED_RANDOMNESS: i32 = 15;
+/// See [`Error::DegenerateKey`].
+pub const BLS_ELGAMAL_ERR_DEGENERATE_KEY: i32 = 16;
+
+/// Map an [`Error`] to its C error code.
+pub fn error_code(e: Error) -> i32 {
+    match e {
+        Error::OutOfRange => BLS_ELGAMAL_ERR_OUT_OF_RANGE,
+        Error::TableTooLarge => BLS_ELGAMAL_ERR_TABLE_TOO_LARGE,
+        Error::GeneratorMismatch => BLS_ELGAMAL_ERR_GENERATOR_MISMATCH,
+        Error::InvalidEncoding => BLS_ELGAMAL_ERR_INVALID_ENCODING,
+        Error::InvalidGenerator => BLS_ELGAMAL_ERR_INVALID_GENERATOR,
+        Error::LengthMismatch => BLS_ELGAMAL_ERR_LENGTH_MISMATCH,
+        Error::UnexpectedCurve => BLS_ELGAMAL_ERR_UNEXPECTED_CURVE,
+        Error::EmptyInput => BLS_ELGAMAL_ERR_EMPTY_INPUT,
+        Error::InvalidRandomness => BLS_ELGAMAL_ERR_INVALID_RANDOMNESS,
+        Error::InvalidLength { .. } => BLS_ELGAMAL_ERR_INVALID_LENGTH,
+        Error::UnsupportedVersion(_) => BLS_ELGAMAL_ERR_UNSUPPORTED_VERSION,
+        Error::CorruptKey => BLS_ELGAMAL_ERR_CORRUPT_KEY,
+        Error::BufferTooSmall { .. } => BLS_ELGAMAL_ERR_BUFFER_TOO_SMALL,
+        Error::AuthenticationFailed => BLS_ELGAMAL_ERR_AUTHENTICATION_FAILED,
+        Error::ReusedRandomness => BLS_ELGAMAL_ERR_REUSED_RANDOMNESS,
+        Error::DegenerateKey => BLS_ELGAMAL_ERR_DEGENERATE_KEY,
+    }
+}
+
+/// Derive a key pair from a 32-byte seed, using the standard G1 generator. The same seed
+/// always gives the same key pair.
+///
+/// # Safety
+///
+/// `seed_ptr` must point to [`BLS_ELGAMAL_SEED_SIZE`] readable bytes, and `out_sk` and
+/// `out_pk` to [`BLS_ELGAMAL_SECRET_KEY_SIZE`] and [`BLS_ELGAMAL_PUBLIC_KEY_SIZE`]
+/// writable bytes respectively.
+#[no_mangle]
+pub unsafe extern "C" fn bls_elgamal_keygen(
+    seed_ptr: *const u8,
+    out_sk: *mut u8,
+    out_pk: *mut u8,
+) -> i32 {
+    if seed_ptr.is_null() || out_sk.is_null() || out_pk.is_null() {
+        return BLS_ELGAMAL_ERR_NULL_POINTER;
+    }
+    let mut seed = [0u8; BLS_ELGAMAL_SEED_SIZE];
+    seed.copy_from_slice(input(seed_ptr, BLS_ELGAMAL_SEED_SIZE));
+
+    let x = Fr::rand(&mut ChaCha20Rng::from_seed(seed));
+    let sk = SecretKey::from_scalar(x);
+
+    let mut sk_bytes = Vec::with_capacity(BLS_ELGAMAL_SECRET_KEY_SIZE);
+    write_scalar(&mut sk_bytes, &sk.inner.secret());
+    write_public_key(&mut sk_bytes, &sk.public_key());
+    let mut pk_bytes = Vec::with_capacity(BLS_ELGAMAL_PUBLIC_KEY_SIZE);
+    write_public_key(&mut pk_bytes, &sk.public_key());
+
+    output(out_sk, BLS_ELGAMAL_SECRET_KEY_SIZE).copy_from_slice(&sk_bytes);
+    output(out_pk, BLS_ELGAMAL_PUBLIC_KEY_SIZE).copy_from_slice(&pk_bytes);
+    BLS_ELGAMAL_OK
+}
+
+/// Encrypt the message at `msg_ptr` with the public key at `pk_ptr` and the randomness
+/// at `r_ptr`, writing the ciphertext to `out_ct`. Like [`PublicKey::try_encrypt`], it
+/// returns [`BLS_ELGAMAL_ERR_INVALID_GENERATOR`] for a key with the identity generator
+/// and [`BLS_ELGAMAL_ERR_DEGENERATE_KEY`] for a key that is the identity.
+///
+/// # Safety
+///
+/// `pk_ptr`, `msg_ptr` and `r_ptr` must point to [`BLS_ELGAMAL_PUBLIC_KEY_SIZE`],
+/// [`BLS_ELGAMAL_MESSAGE_SIZE`] and [`BLS_ELGAMAL_SCALAR_SIZE`] readable bytes, and
+/// `out_ct` to [`BLS_ELGAMAL_CIPHERTEXT_SIZE`] writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bls_elgamal_encrypt(
+    pk_ptr: *const u8,
+    msg_ptr: *const u8,
+    r_ptr: *const u8,
+    out_ct: *mut u8,
+) -> i32 {
+    if pk_ptr.is_null() || msg_ptr.is_null() || r_ptr.is_null() || out_ct.is_null() {
+        return BLS_ELGAMAL_ERR_NULL_POINTER;
+    }
+    let result = (|| {
+        let pk = PublicKey::try_from(input(pk_ptr, BLS_ELGAMAL_PUBLIC_KEY_SIZE))?;
+        let m = read_point(input(msg_ptr, BLS_ELGAMAL_MESSAGE_SIZE))?;
+        let r = read_scalar(input(r_ptr, BLS_ELGAMAL_SCALAR_SIZE))?;
+        pk.try_encrypt(m, r)
+    })();
+
+    match result {
+        Ok(ct) => {
+            let mut bytes = Vec::with_capacity(BLS_ELGAMAL_CIPHERTEXT_SIZE);
+            write_point(&mut bytes, &ct.0.into());
+            write_point(&mut bytes, &ct.1.into());
+            output(out_ct, BLS_ELGAMAL_CIPHERTEXT_SIZE).copy_from_slice(&bytes);
+            BLS_ELGAMAL_OK
+        }
+        Err(e) => error_code(e),
+    }
+}
+
+/// Decrypt the ciphertext at `ct_ptr` with the secret key at `sk_ptr`, writing the
+/// message to `out_msg`.
+///
+/// # Safety
+///
+/// `sk_ptr` and `ct_ptr` must point to [`BLS_ELGAMAL_SECRET_KEY_SIZE`] and
+/// [`BLS_ELGAMAL_CIPHERTEXT_SIZE`] readable bytes, and `out_msg` to
+/// [`BLS_ELGAMAL_MESSAGE_SIZE`] writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bls_elgamal_decrypt(
+    sk_ptr: *const u8,
+    ct_ptr: *const u8,
+    out_msg: *mut u8,
+) -> i32 {
+    if sk_ptr.is_null() || ct_ptr.is_null() || out_msg.is_null() {
+        return BLS_ELGAMAL_ERR_NULL_POINTER;
+    }
+    let result = (|| {
+        let sk = SecretKey::try_from(input(sk_ptr, BLS_ELGAMAL_SECRET_KEY_SIZE))?;
+
+        let ct_bytes = input(ct_ptr, BLS_ELGAMAL_CIPHERTEXT_SIZE);
+        let a = read_point(&ct_bytes[..BLS_ELGAMAL_MESSAGE_SIZE])?;
+        let b = read_point(&ct_bytes[BLS_ELGAMAL_MESSAGE_SIZE..])?;
+        Ok(sk.decrypt(Ciphertext(a.into(), b.into())))
+    })();
+
+    match result {
+        Ok(m) => {
+            let mut bytes = Vec::with_capacity(BLS_ELGAMAL_MESSAGE_SIZE);
+            write_point(&mut bytes, &m);
+            output(out_msg, BLS_ELGAMAL_MESSAGE_SIZE).copy_from_slice(&bytes);
+            BLS_ELGAMAL_OK
+        }
+        Err(e) => error_code(e),
+    }
+}
+
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    std::slice::from_raw_parts(ptr, len)
+}
+
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> &'a mut [u8] {
+    std::slice::from_raw_parts_mut(ptr, len)
+}
+
+fn read_point(bytes: &[u8]) -> Result<G1Affine, Error> {
+    G1Affine::deserialize_compressed(bytes).map_err(|_| Error::InvalidEncoding)
+}
+
+fn read_scalar(bytes: &[u8]) -> Result<Fr, Error> {
+    Fr::deserialize_compressed(bytes).map_err(|_| Error::InvalidEncoding)
+}
+
+fn write_point(bytes: &mut Vec<u8>, p: &G1Affine) {
+    p.serialize_compressed(bytes)
+        .expect("serializing to a vector cannot fail");
+}
+
+fn write_scalar(bytes: &mut Vec<u8>, s: &Fr) {
+    s.serialize_compressed(bytes)
+        .expect("serializing to a vector cannot fail");
+}
+
+fn write_public_key(bytes: &mut Vec<u8>, pk: &PublicKey) {
+    write_point(bytes, &pk.generator());
+    write_point(bytes, &pk.y());
+}
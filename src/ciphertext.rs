@@ -1,13 +1,237 @@
-use std::ops::Add;
+use std::io::{self, Read, Write};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
-use ark_ec::CurveGroup;
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid, Validate};
 use serde::{Deserialize, Serialize};
 
+use sha2::Sha256;
+
+use super::{
+    error::Error,
+    format::{self, SerializationMode, Uncompressed},
+    pedersen::PedersenParams,
+    proof::hash_to_scalar,
+};
+
+/// The domain separation tag of the hash of [`Ciphertext::commit`].
+const COMMIT_DOMAIN: &[u8] = b"bls-elgamal/ciphertext-commitment";
+
+/// The number of ciphertexts [`Ciphertext::read_many`] and [`Ciphertext::write_many`]
+/// process at once.
+const IO_CHUNK: usize = 4096;
+
 /// A ciphertext is a pair of two points.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 // (rG, m + rY)
 pub struct Ciphertext<G: CurveGroup>(pub G, pub G);
 
+impl<G: CurveGroup> Ciphertext<G> {
+    /// Get the two points `[a, b]` of the ciphertext, e.g. to stack a vector of
+    /// ciphertexts into the rows of a matrix for batched scalar multiplication.
+    pub fn as_array(&self) -> [G; 2] {
+        [self.0, self.1]
+    }
+
+    /// Create a ciphertext from the two points `[a, b]`, the inverse of
+    /// [`Ciphertext::as_array`].
+    pub fn from_array([a, b]: [G; 2]) -> Self {
+        Ciphertext(a, b)
+    }
+
+    /// Read `count` ciphertexts written by [`Ciphertext::write_many`], i.e. fixed-width
+    /// records of the two compressed points without any framing.
+    ///
+    /// The points are decompressed without validation and then checked to be on the curve
+    /// and in the prime-order subgroup in batches, which is faster than deserializing
+    /// each ciphertext on its own. Malformed records return an error of kind
+    /// [`io::ErrorKind::InvalidData`] wrapping [`Error::InvalidEncoding`].
+    pub fn read_many<R: Read>(mut reader: R, count: usize) -> io::Result<Vec<Self>> {
+        let point_size = G::Affine::generator().compressed_size();
+        let mut cts = Vec::with_capacity(count.min(IO_CHUNK));
+        let mut buf = Vec::new();
+        let mut remaining = count;
+        while remaining > 0 {
+            let n = remaining.min(IO_CHUNK);
+            buf.resize(2 * n * point_size, 0);
+            reader.read_exact(&mut buf)?;
+
+            let points = buf
+                .chunks_exact(point_size)
+                .map(G::Affine::deserialize_compressed_unchecked)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid_data())?;
+            G::Affine::batch_check(points.iter()).map_err(|_| invalid_data())?;
+            cts.extend(
+                points
+                    .chunks_exact(2)
+                    .map(|p| Ciphertext(p[0].into(), p[1].into())),
+            );
+            remaining -= n;
+        }
+        Ok(cts)
+    }
+
+    /// Write the ciphertexts as fixed-width records of their two compressed points, to be
+    /// read by [`Ciphertext::read_many`]. The number of ciphertexts is not written.
+    pub fn write_many<W: Write>(mut writer: W, cts: &[Self]) -> io::Result<()> {
+        let mut buf = Vec::new();
+        for chunk in cts.chunks(IO_CHUNK) {
+            let points = chunk.iter().flat_map(|ct| [ct.0, ct.1]).collect::<Vec<_>>();
+            buf.clear();
+            count_ops!(NORMALIZATIONS);
+            G::normalize_batch(&points)
+                .iter()
+                .try_for_each(|p| p.serialize_compressed(&mut buf))
+                .map_err(|_| invalid_data())?;
+            writer.write_all(&buf)?;
+        }
+        Ok(())
+    }
+
+    /// Check whether the ciphertext is degenerate, i.e. a is the identity. Then the
+    /// randomness was zero and b is the plaintext itself, so applications should reject
+    /// such ciphertexts from external sources.
+    pub fn is_degenerate(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Commit to the ciphertext with the blinding `blinding`, i.e. the Pedersen commitment
+    /// vG + sH (see [`PedersenParams::commit`]) to the SHA-256 hash v of the compressed
+    /// points a || b, e.g. to store ciphertexts in a public Merkle tree until they are
+    /// revealed. The commitment opens by recomputing it from the revealed ciphertext and
+    /// blinding.
+    ///
+    /// It is hiding for a random blinding, and binding as long as the discrete log of H
+    /// is unknown and the hash is collision resistant, i.e. no other ciphertext has the
+    /// same commitment except with negligible probability.
+    pub fn commit(
+        &self,
+        blinding: <G as PrimeGroup>::ScalarField,
+        params: &PedersenParams<G>,
+    ) -> G::Affine {
+        count_ops!(NORMALIZATIONS);
+        let mut bytes = Vec::new();
+        G::normalize_batch(&[self.0, self.1])
+            .serialize_compressed(&mut bytes)
+            .expect("serializing to a vector cannot fail");
+        params.commit(hash_to_scalar::<Sha256, _>(COMMIT_DOMAIN, &bytes), blinding)
+    }
+
+    /// Negate both points in place, turning the ciphertext into an encryption of the
+    /// negated message -m, e.g. to conditionally subtract in an accumulation loop.
+    pub fn negate(&mut self) {
+        self.0 = -self.0;
+        self.1 = -self.1;
+    }
+
+    /// Sum the ciphertexts homomorphically, e.g. to tally votes. The sum is accumulated in
+    /// projective form from references to the ciphertexts, and both points of the result
+    /// are normalized once at the end, so it has the canonical (affine) representation.
+    pub fn sum_slice(cts: &[Self]) -> Self {
+        let sum: Ciphertext<G> = cts.iter().map(Ciphertext::view).sum();
+        count_ops!(NORMALIZATIONS);
+        let points = G::normalize_batch(&[sum.0, sum.1]);
+        Ciphertext(points[0].into(), points[1].into())
+    }
+
+    /// Compute the linear combination of the ciphertexts with public weights, i.e. an
+    /// encryption of w_1 m_1 + ... + w_n m_n for the terms (w_i, ct_i), without
+    /// decrypting. With w_i = z^i, it evaluates an encrypted polynomial at z. Both
+    /// components are computed with a multi-scalar multiplication, and the combination
+    /// of no terms is the encryption of the identity with no randomness.
+    pub fn linear_combination(terms: &[(<G as PrimeGroup>::ScalarField, Self)]) -> Self {
+        let weights: Vec<_> = terms.iter().map(|(w, _)| *w).collect();
+        let points: Vec<G> = terms.iter().flat_map(|(_, ct)| [ct.0, ct.1]).collect();
+        count_ops!(NORMALIZATIONS);
+        count_ops!(SCALAR_MULS, 2 * terms.len());
+        let points = G::normalize_batch(&points);
+        let (a, b): (Vec<_>, Vec<_>) = points.chunks_exact(2).map(|p| (p[0], p[1])).unzip();
+        Ciphertext(
+            G::msm_unchecked(&a, &weights),
+            G::msm_unchecked(&b, &weights),
+        )
+    }
+
+    /// Multiply the ciphertext by the integer k given by its bits `bits`, most significant
+    /// first, with double-and-add over both points. The result equals `ct * k` for k as a
+    /// scalar, and decrypts to km, e.g. when k arrives bit by bit from an MPC.
+    ///
+    /// The number of additions depends on the bits, so the timing leaks the Hamming
+    /// weight of k.
+    pub fn mul_bits(&self, bits: &[bool]) -> Self {
+        let mut res = Self::default();
+        for &bit in bits {
+            res.0.double_in_place();
+            res.1.double_in_place();
+            if bit {
+                res += self.view();
+            }
+        }
+        res
+    }
+
+    /// Replace the message component b with `new_b`, keeping a = rG, for protocols where
+    /// a is fixed out of band and only b changes across rounds.
+    ///
+    /// This is a low-level setter that breaks the encryption invariant b = m + rY: the
+    /// result only decrypts to a meaningful message if `new_b` is m' + rY for the same r
+    /// and key, which the caller must ensure (e.g. b + (m' - m)). Reusing a with
+    /// different messages also reveals their differences to anyone who learns one of
+    /// them.
+    pub fn replace_b(&self, new_b: G::Affine) -> Self {
+        Ciphertext(self.0, new_b.into())
+    }
+
+    /// Apply the point map `f` to both points of the ciphertext, e.g. to experiment with
+    /// an endomorphism of the curve.
+    ///
+    /// The result decrypts to f(m) under the same key only if `f` is a group
+    /// homomorphism that commutes with scalar multiplication, i.e. f(P + Q) = f(P) + f(Q)
+    /// and f(kP) = k f(P). This holds for multiplication by a fixed scalar or the GLV
+    /// endomorphism of a prime-order group, but not for an arbitrary map, and it is not
+    /// checked.
+    pub fn map_points<F: Fn(G) -> G>(&self, f: F) -> Self {
+        Ciphertext(f(self.0), f(self.1))
+    }
+
+    /// Borrow the two points of the ciphertext.
+    pub fn view(&self) -> CiphertextRef<'_, G> {
+        CiphertextRef(&self.0, &self.1)
+    }
+}
+
+/// A ciphertext borrowing its two points, to accumulate ciphertexts (e.g. over a large
+/// slice) without copying each of them.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CiphertextRef<'a, G: CurveGroup>(pub &'a G, pub &'a G);
+
+impl<G: CurveGroup> CiphertextRef<'_, G> {
+    /// Copy the points into an owned ciphertext.
+    pub fn to_ciphertext(&self) -> Ciphertext<G> {
+        Ciphertext(*self.0, *self.1)
+    }
+}
+
+impl<'a, G: CurveGroup> From<&'a Ciphertext<G>> for CiphertextRef<'a, G> {
+    fn from(ct: &'a Ciphertext<G>) -> Self {
+        ct.view()
+    }
+}
+
+fn invalid_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, Error::InvalidEncoding)
+}
+
+impl<G: CurveGroup> Default for Ciphertext<G> {
+    /// The encryption of the identity with no randomness, (0, 0), which is the identity
+    /// of the homomorphic addition and decrypts to the identity under any key.
+    fn default() -> Self {
+        Ciphertext(G::zero(), G::zero())
+    }
+}
+
 // Implement homomorphic addition for Ciphertext
 
 impl<G: CurveGroup> Add for Ciphertext<G> {
@@ -42,22 +266,210 @@ impl<G: CurveGroup> Add<Ciphertext<G>> for &Ciphertext<G> {
     }
 }
 
+impl<G: CurveGroup> Add for CiphertextRef<'_, G> {
+    type Output = Ciphertext<G>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Ciphertext(*self.0 + rhs.0, *self.1 + rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Add<CiphertextRef<'_, G>> for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn add(self, rhs: CiphertextRef<'_, G>) -> Self::Output {
+        Ciphertext(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<G: CurveGroup> AddAssign<CiphertextRef<'_, G>> for Ciphertext<G> {
+    fn add_assign(&mut self, rhs: CiphertextRef<'_, G>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<G: CurveGroup> Sum for Ciphertext<G> {
+    fn sum<I: Iterator<Item = Ciphertext<G>>>(iter: I) -> Self {
+        iter.fold(Ciphertext::default(), |acc, ct| acc + ct)
+    }
+}
+
+impl<'a, G: CurveGroup> Sum<CiphertextRef<'a, G>> for Ciphertext<G> {
+    fn sum<I: Iterator<Item = CiphertextRef<'a, G>>>(iter: I) -> Self {
+        iter.fold(Ciphertext::default(), |acc, ct| acc + ct)
+    }
+}
+
+// Implement homomorphic subtraction for Ciphertext
+
+impl<G: CurveGroup> Sub for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: Self) -> Self {
+        Ciphertext(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Sub for &Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Ciphertext(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Sub<&Ciphertext<G>> for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Ciphertext(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Sub<Ciphertext<G>> for &Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: Ciphertext<G>) -> Self::Output {
+        Ciphertext(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Sub for CiphertextRef<'_, G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Ciphertext(*self.0 - rhs.0, *self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> Sub<CiphertextRef<'_, G>> for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn sub(self, rhs: CiphertextRef<'_, G>) -> Self::Output {
+        Ciphertext(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<G: CurveGroup> SubAssign<CiphertextRef<'_, G>> for Ciphertext<G> {
+    fn sub_assign(&mut self, rhs: CiphertextRef<'_, G>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+// Implement homomorphic negation for Ciphertext
+
+impl<G: CurveGroup> Neg for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn neg(mut self) -> Self::Output {
+        self.negate();
+        self
+    }
+}
+
+impl<G: CurveGroup> Neg for &Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn neg(self) -> Self::Output {
+        Ciphertext(-self.0, -self.1)
+    }
+}
+
+// Implement homomorphic scalar multiplication for Ciphertext
+
+impl<G: CurveGroup> Mul<<G as PrimeGroup>::ScalarField> for Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn mul(self, rhs: <G as PrimeGroup>::ScalarField) -> Self::Output {
+        count_ops!(SCALAR_MULS, 2);
+        Ciphertext(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<G: CurveGroup> Mul<<G as PrimeGroup>::ScalarField> for &Ciphertext<G> {
+    type Output = Ciphertext<G>;
+
+    fn mul(self, rhs: <G as PrimeGroup>::ScalarField) -> Self::Output {
+        count_ops!(SCALAR_MULS, 2);
+        Ciphertext(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+// The reflected multiplication k * ct is only implemented for BLS12-381 G1, since the
+// scalar field is a foreign type. The types are the same as `crate::{Fr, G1}`, named
+// directly because trait coherence does not see through the associated type projections
+// of those aliases.
+
+impl Mul<Ciphertext<ark_bls12_381::G1Projective>> for ark_bls12_381::Fr {
+    type Output = Ciphertext<ark_bls12_381::G1Projective>;
+
+    fn mul(self, rhs: Ciphertext<ark_bls12_381::G1Projective>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<&Ciphertext<ark_bls12_381::G1Projective>> for ark_bls12_381::Fr {
+    type Output = Ciphertext<ark_bls12_381::G1Projective>;
+
+    fn mul(self, rhs: &Ciphertext<ark_bls12_381::G1Projective>) -> Self::Output {
+        rhs * self
+    }
+}
+
+// Implement arbitrary for Ciphertext
+
+#[cfg(feature = "arbitrary")]
+impl<'a, G: CurveGroup> arbitrary::Arbitrary<'a> for Ciphertext<G> {
+    /// Generate a ciphertext whose points are multiples of the group generator, so
+    /// that both points are always valid and in the prime-order subgroup.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let a = G::generator() * crate::arbitrary_scalar::<G>(u)?;
+        let b = G::generator() * crate::arbitrary_scalar::<G>(u)?;
+        Ok(Ciphertext(a, b))
+    }
+}
+
 // Implement serialization and deserialization for Ciphertext
 
-impl<G: CurveGroup> Serialize for Ciphertext<G> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<G: CurveGroup> Ciphertext<G> {
+    fn serialize_with_mode<S>(
+        &self,
+        mode: SerializationMode,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         let mut bytes = Vec::new();
         self.0
-            .serialize_compressed(&mut bytes)
+            .serialize_with_mode(&mut bytes, mode.into())
             .map_err(|_| serde::ser::Error::custom("Failed to serialize the first point"))?;
         self.1
-            .serialize_compressed(&mut bytes)
+            .serialize_with_mode(&mut bytes, mode.into())
             .map_err(|_| serde::ser::Error::custom("Failed to serialize the second point"))?;
 
-        serializer.serialize_bytes(&bytes)
+        serializer.serialize_bytes(&format::frame::<G>(mode, &bytes))
+    }
+}
+
+impl<G: CurveGroup> Serialize for Ciphertext<G> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.serialize_with_mode(SerializationMode::Compressed, serializer)
+    }
+}
+
+impl<G: CurveGroup> Serialize for Uncompressed<Ciphertext<G>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0
+            .serialize_with_mode(SerializationMode::Uncompressed, serializer)
     }
 }
 
@@ -67,12 +479,11 @@ impl<'de, G: CurveGroup> Deserialize<'de> for Ciphertext<G> {
         D: serde::Deserializer<'de>,
     {
         let bytes: Vec<u8> = Vec::deserialize(deserializer)?;
+        let (mode, mut bytes) = format::unframe::<G>(&bytes).map_err(serde::de::Error::custom)?;
 
-        let a = G::deserialize_compressed(&bytes[..])
+        let a = G::deserialize_with_mode(&mut bytes, mode.into(), Validate::Yes)
             .map_err(|_| serde::de::Error::custom("Failed to deserialize the first point"))?;
-
-        let a_size = a.serialized_size(ark_serialize::Compress::Yes);
-        let b = G::deserialize_compressed(&bytes[a_size..])
+        let b = G::deserialize_with_mode(&mut bytes, mode.into(), Validate::Yes)
             .map_err(|_| serde::de::Error::custom("Failed to deserialize the second point"))?;
 
         Ok(Ciphertext(a, b))
@@ -0,0 +1,25 @@
+use ark_bls12_381::Fq;
+use ark_ff::PrimeField;
+use subtle::{Choice, ConstantTimeEq};
+
+use super::{G1Affine, G1};
+
+/// Compare the projective point `p` with the affine point `q` in constant time.
+///
+/// The projective point is never normalized: its Jacobian coordinates (X, Y, Z) are
+/// compared with (x, y) by checking X = xZ^2 and Y = yZ^3, so no field inversion is
+/// performed on `p`.
+pub(crate) fn ct_eq_projective(p: &G1, q: &G1Affine) -> Choice {
+    let z2 = p.z * p.z;
+    let z3 = z2 * p.z;
+    let x_eq = fq_ct_eq(&p.x, &(q.x * z2));
+    let y_eq = fq_ct_eq(&p.y, &(q.y * z3));
+    let p_is_zero = fq_ct_eq(&p.z, &Fq::from(0u64));
+    let q_is_zero = Choice::from(q.infinity as u8);
+    (x_eq & y_eq & !p_is_zero & !q_is_zero) | (p_is_zero & q_is_zero)
+}
+
+fn fq_ct_eq(a: &Fq, b: &Fq) -> Choice {
+    let (a, b) = (a.into_bigint().0, b.into_bigint().0);
+    a.ct_eq(&b)
+}
@@ -1,9 +1,18 @@
-use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_ec::{scalar_mul::BatchMulPreprocessing, AffineRepr, CurveGroup, PrimeGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Validate};
 use serde::{Deserialize, Serialize};
-use std::ops::Neg;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
-use super::{ciphertext::Ciphertext, encrypt::EncryptKey};
+use super::{
+    bsgs::BsgsTable,
+    ciphertext::Ciphertext,
+    encrypt::EncryptKey,
+    error::Error,
+    format::{self, SerializationMode, Uncompressed},
+    rekey::ReKey,
+    scalar_mul::{ScalarMul, SoftwareScalarMul},
+};
 
 /// A key to decrypt a message.
 ///
@@ -17,8 +26,11 @@ pub struct DecryptKey<G: CurveGroup> {
 
 impl<G: CurveGroup> DecryptKey<G> {
     /// Create a new decryption key with group generator `generator` and secret `x`.
+    ///
+    /// The generator is not checked, see [`DecryptKey::try_new`].
     pub fn new(generator: G::Affine, x: <G as PrimeGroup>::ScalarField) -> Self {
         let generator = generator.into_group();
+        count_ops!(SCALAR_MULS);
         let y = generator * x;
         Self {
             secret: x,
@@ -26,9 +38,219 @@ impl<G: CurveGroup> DecryptKey<G> {
         }
     }
 
+    /// Create a new decryption key with the standard generator of the group and secret
+    /// `x`.
+    pub fn from_scalar(x: <G as PrimeGroup>::ScalarField) -> Self {
+        Self::new(G::generator().into_affine(), x)
+    }
+
+    /// Create a new decryption key with group generator `generator` and secret `x`, or
+    /// return [`Error::InvalidGenerator`] if the generator is the identity (which would
+    /// make every public key the identity as well).
+    pub fn try_new(generator: G::Affine, x: <G as PrimeGroup>::ScalarField) -> Result<Self, Error> {
+        if generator.is_zero() {
+            return Err(Error::InvalidGenerator);
+        }
+        Ok(Self::new(generator, x))
+    }
+
     /// Decrypt a ciphertext (a, b) to get b - ax.
     pub fn decrypt(&self, ct: Ciphertext<G>) -> G::Affine {
-        (ct.1 + ct.0 * self.secret.neg()).into()
+        self.decrypt_with(&SoftwareScalarMul, ct)
+    }
+
+    /// Decrypt a ciphertext (a, b) to get b - ax like [`DecryptKey::decrypt`], computing
+    /// ax with the scalar multiplication backend `backend`.
+    pub fn decrypt_with<M: ScalarMul<G>>(&self, backend: &M, ct: Ciphertext<G>) -> G::Affine {
+        count_ops!(NORMALIZATIONS);
+        self.decrypt_projective_with(backend, ct).into()
+    }
+
+    /// Decrypt a ciphertext (a, b) to get b - ax in projective form, without the field
+    /// inversion of the conversion to affine form.
+    pub fn decrypt_projective(&self, ct: Ciphertext<G>) -> G {
+        self.decrypt_projective_with(&SoftwareScalarMul, ct)
+    }
+
+    /// Decrypt a ciphertext in projective form like [`DecryptKey::decrypt_projective`],
+    /// computing ax with the scalar multiplication backend `backend`.
+    pub fn decrypt_projective_with<M: ScalarMul<G>>(&self, backend: &M, ct: Ciphertext<G>) -> G {
+        count_ops!(SCALAR_MULS);
+        ct.1 - backend.mul(&ct.0, &self.secret)
+    }
+
+    /// Decrypt a ciphertext and write the compressed encoding of the message to the
+    /// start of `out`, without allocating. Returns the number of bytes written, or
+    /// [`Error::BufferTooSmall`] if `out` cannot hold the encoding, leaving it untouched.
+    pub fn decrypt_into(&self, ct: Ciphertext<G>, out: &mut [u8]) -> Result<usize, Error> {
+        let m = self.decrypt(ct);
+        let needed = m.compressed_size();
+        if out.len() < needed {
+            return Err(Error::BufferTooSmall {
+                needed,
+                got: out.len(),
+            });
+        }
+        m.serialize_compressed(&mut out[..needed])
+            .expect("the buffer has the size of the message");
+        Ok(needed)
+    }
+
+    /// Decrypt the ciphertexts, normalizing all the messages in one batch, which is
+    /// faster than decrypting each ciphertext on its own.
+    pub fn decrypt_batch(&self, cts: &[Ciphertext<G>]) -> Vec<G::Affine> {
+        self.decrypt_batch_with(&SoftwareScalarMul, cts)
+    }
+
+    /// Decrypt the ciphertexts in one batch like [`DecryptKey::decrypt_batch`], computing
+    /// the products ax with the scalar multiplication backend `backend`.
+    pub fn decrypt_batch_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        cts: &[Ciphertext<G>],
+    ) -> Vec<G::Affine> {
+        count_ops!(NORMALIZATIONS);
+        let points: Vec<G> = cts
+            .iter()
+            .map(|ct| self.decrypt_projective_with(backend, *ct))
+            .collect();
+        G::normalize_batch(&points)
+    }
+
+    /// Decrypt the ciphertext `ct` with each of the keys `keys`, e.g. when it was
+    /// encrypted to an unknown one of them, returning the messages in the order of the
+    /// keys. Only the message of the right key is meaningful.
+    ///
+    /// The products a x_i all share the base a, so the multiples of a are precomputed
+    /// once and every key costs only additions. It pays off over
+    /// [`DecryptKey::decrypt`] from a few dozen keys on.
+    pub fn decrypt_try_keys(ct: Ciphertext<G>, keys: &[DecryptKey<G>]) -> Vec<G::Affine> {
+        count_ops!(SCALAR_MULS, keys.len());
+        count_ops!(NORMALIZATIONS, 2);
+        let secrets: Vec<_> = keys.iter().map(|k| k.secret).collect();
+        let table = BatchMulPreprocessing::new(ct.0, keys.len());
+        let points: Vec<G> = table
+            .batch_mul(&secrets)
+            .into_iter()
+            .map(|xa| ct.1 - xa)
+            .collect();
+        G::normalize_batch(&points)
+    }
+
+    /// Decrypt the difference of two messages from their ciphertexts `ct1` and `ct2`,
+    /// i.e. dec(ct1) - dec(ct2) = dec(ct1 - ct2), with a single decryption.
+    pub fn decrypt_difference(&self, ct1: Ciphertext<G>, ct2: Ciphertext<G>) -> G::Affine {
+        self.decrypt(ct1 - ct2)
+    }
+
+    /// Decrypt a ciphertext once and check whether the message is in the set `set`.
+    pub fn decrypt_in_set(&self, ct: Ciphertext<G>, set: &HashSet<G::Affine>) -> bool {
+        set.contains(&self.decrypt(ct))
+    }
+
+    /// Decrypt a ciphertext of a small integer created by
+    /// [`EncryptKey::encrypt_scalar`], recovering the integer with the table `table`.
+    ///
+    /// Returns [`Error::OutOfRange`] if the integer is larger than the maximum of the table.
+    pub fn decrypt_scalar(&self, ct: Ciphertext<G>, table: &BsgsTable<G>) -> Result<u64, Error> {
+        self.decrypt_scalar_with(&SoftwareScalarMul, ct, table)
+    }
+
+    /// Decrypt a ciphertext of a small integer like [`DecryptKey::decrypt_scalar`],
+    /// computing ax with the scalar multiplication backend `backend`.
+    pub fn decrypt_scalar_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G>,
+        table: &BsgsTable<G>,
+    ) -> Result<u64, Error> {
+        table.discrete_log(self.decrypt_projective_with(backend, ct))
+    }
+
+    /// Compare the small integers encrypted in the ciphertexts `a` and `b` (see
+    /// [`DecryptKey::decrypt_scalar`]). Returns `None` if either is out of the range of
+    /// `table`.
+    pub fn cmp_ciphertexts(
+        &self,
+        a: Ciphertext<G>,
+        b: Ciphertext<G>,
+        table: &BsgsTable<G>,
+    ) -> Option<Ordering> {
+        let a = self.decrypt_scalar(a, table).ok()?;
+        let b = self.decrypt_scalar(b, table).ok()?;
+        Some(a.cmp(&b))
+    }
+
+    /// Decrypt a ciphertext of a small signed integer `v` in `[-max, max]`, where `max`
+    /// is the maximum of the table `table` (see [`EncryptKey::encrypt_signed`]). Returns
+    /// [`Error::OutOfRange`] if `v` is not in the range.
+    pub fn decrypt_signed(&self, ct: Ciphertext<G>, table: &BsgsTable<G>) -> Result<i64, Error> {
+        self.decrypt_signed_with(&SoftwareScalarMul, ct, table)
+    }
+
+    /// Decrypt a ciphertext of a small signed integer like [`DecryptKey::decrypt_signed`],
+    /// computing ax with the scalar multiplication backend `backend`.
+    pub fn decrypt_signed_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G>,
+        table: &BsgsTable<G>,
+    ) -> Result<i64, Error> {
+        table.discrete_log_signed(self.decrypt_projective_with(backend, ct))
+    }
+
+    /// Decrypt a ciphertext of a small integer `v` (see [`DecryptKey::decrypt_scalar`])
+    /// to get both the decrypted point vG and `v`.
+    pub fn decrypt_value(
+        &self,
+        ct: Ciphertext<G>,
+        table: &BsgsTable<G>,
+    ) -> Result<(G::Affine, u64), Error> {
+        self.decrypt_value_with(&SoftwareScalarMul, ct, table)
+    }
+
+    /// Decrypt a ciphertext of a small integer to get vG and `v` like
+    /// [`DecryptKey::decrypt_value`], computing ax with the scalar multiplication backend
+    /// `backend`.
+    pub fn decrypt_value_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G>,
+        table: &BsgsTable<G>,
+    ) -> Result<(G::Affine, u64), Error> {
+        let point = self.decrypt_projective_with(backend, ct);
+        let v = table.discrete_log(point)?;
+        count_ops!(NORMALIZATIONS);
+        Ok((point.into_affine(), v))
+    }
+
+    /// Create a re-encryption key from this key to the key `to`, which must have the
+    /// same generator.
+    pub fn rekey(&self, to: &DecryptKey<G>) -> Result<ReKey<G>, Error> {
+        if !self.encrypt_key.same_generator(&to.encrypt_key) {
+            return Err(Error::GeneratorMismatch);
+        }
+        Ok(ReKey {
+            delta: to.secret - self.secret,
+        })
+    }
+
+    /// Combine the keys into the aggregate key with secret x1 + ... + xn, which decrypts
+    /// ciphertexts under the aggregate public key of
+    /// [`EncryptKey::combine`]. It requires all parties to reveal their secrets.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator.
+    pub fn combine_secret(keys: &[DecryptKey<G>]) -> Result<Self, Error> {
+        let (first, rest) = keys.split_first().ok_or(Error::EmptyInput)?;
+        if rest
+            .iter()
+            .any(|k| !k.encrypt_key.same_generator(&first.encrypt_key))
+        {
+            return Err(Error::GeneratorMismatch);
+        }
+        let secret = keys.iter().map(|k| k.secret).sum();
+        Ok(Self::new(first.encrypt_key.generator.into_affine(), secret))
     }
 
     /// Get the encrypt key.
@@ -36,44 +258,238 @@ impl<G: CurveGroup> DecryptKey<G> {
         &self.encrypt_key
     }
 
+    /// Check that the public key y of the encrypt key is the generator times the secret,
+    /// i.e. that ciphertexts encrypted to the encrypt key decrypt with this key. It fails
+    /// for keys whose y was corrupted, e.g. in storage.
+    pub fn validate(&self) -> bool {
+        count_ops!(SCALAR_MULS);
+        self.encrypt_key.y == self.encrypt_key.generator * self.secret
+    }
+
     /// Get the scalar field secret (x).
     pub fn secret(&self) -> <G as PrimeGroup>::ScalarField {
         self.secret
     }
+
+    /// Serialize the secret x alone as its canonical little-endian bytes, i.e. fully
+    /// reduced modulo the order of the scalar field.
+    pub fn secret_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.secret
+            .serialize_compressed(&mut bytes)
+            .expect("serializing to a vector cannot fail");
+        bytes
+    }
+
+    /// Create a key with the standard generator of the group from the secret serialized
+    /// by [`DecryptKey::secret_bytes`]. Returns [`Error::InvalidEncoding`] if the bytes
+    /// have the wrong length or are not the canonical encoding of a scalar (i.e. not
+    /// reduced), rather than reducing them.
+    pub fn from_secret_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+        let secret = <G as PrimeGroup>::ScalarField::deserialize_compressed(&mut reader)
+            .map_err(|_| Error::InvalidEncoding)?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(Self::from_scalar(secret))
+    }
+
+    /// Serialize the key as the compressed generator followed by the secret, leaving out
+    /// the public key y, which is derived again by [`DecryptKey::from_bytes_compact`].
+    pub fn to_bytes_compact(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encrypt_key
+            .generator
+            .into_affine()
+            .serialize_compressed(&mut bytes)
+            .expect("serializing to a vector cannot fail");
+        self.secret
+            .serialize_compressed(&mut bytes)
+            .expect("serializing to a vector cannot fail");
+        bytes
+    }
+
+    /// Deserialize a key serialized by [`DecryptKey::to_bytes_compact`]. Returns
+    /// [`Error::InvalidEncoding`] if the bytes are malformed, or
+    /// [`Error::InvalidGenerator`] if the generator is the identity.
+    pub fn from_bytes_compact(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+        let generator =
+            G::Affine::deserialize_compressed(&mut reader).map_err(|_| Error::InvalidEncoding)?;
+        let secret = <G as PrimeGroup>::ScalarField::deserialize_compressed(&mut reader)
+            .map_err(|_| Error::InvalidEncoding)?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+        Self::try_new(generator, secret)
+    }
 }
 
-impl<G: CurveGroup> Serialize for DecryptKey<G> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+#[cfg(feature = "arbitrary")]
+impl<'a, G: CurveGroup> arbitrary::Arbitrary<'a> for DecryptKey<G> {
+    /// Generate a key with a non-identity generator and a non-zero secret.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use ark_std::{One, Zero};
+
+        let non_zero = |s: <G as PrimeGroup>::ScalarField| if s.is_zero() { One::one() } else { s };
+        let generator = G::generator() * non_zero(crate::arbitrary_scalar::<G>(u)?);
+        let x = non_zero(crate::arbitrary_scalar::<G>(u)?);
+        Ok(DecryptKey::new(generator.into_affine(), x))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<G: CurveGroup> DecryptKey<G> {
+    /// Decrypt the ciphertexts received from `rx` and send the messages to `tx`, in
+    /// order, until `rx` is closed and drained or `tx` is closed.
+    ///
+    /// Ciphertexts are decrypted in batches with [`DecryptKey::decrypt_batch`]. A batch
+    /// starts with the next ciphertext received and ends when it has `max_batch`
+    /// ciphertexts or `max_wait` has passed since its start, so a message is delayed by
+    /// at most `max_wait` plus the time to decrypt its batch.
+    ///
+    /// Each batch costs one scalar multiplication per ciphertext, so it is decrypted on
+    /// the blocking thread pool of the runtime with `tokio::task::spawn_blocking` rather
+    /// than on the worker thread running the service. This requires a Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime shuts down while a batch is being decrypted.
+    pub async fn decrypt_service(
+        &self,
+        mut rx: tokio::sync::mpsc::Receiver<Ciphertext<G>>,
+        tx: tokio::sync::mpsc::Sender<G::Affine>,
+        max_batch: usize,
+        max_wait: std::time::Duration,
+    ) {
+        let max_batch = max_batch.max(1);
+        let mut batch = Vec::with_capacity(max_batch);
+        while let Some(ct) = rx.recv().await {
+            batch.push(ct);
+            let deadline = tokio::time::Instant::now() + max_wait;
+            while batch.len() < max_batch {
+                match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(Some(ct)) => batch.push(ct),
+                    // closed or timed out, decrypt what is there
+                    Ok(None) | Err(_) => break,
+                }
+            }
+            let key = *self;
+            let messages;
+            (messages, batch) = tokio::task::spawn_blocking(move || {
+                let messages = key.decrypt_batch(&batch);
+                batch.clear();
+                (messages, batch)
+            })
+            .await
+            .expect("decrypting a batch does not panic");
+            for m in messages {
+                if tx.send(m).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<G: CurveGroup> DecryptKey<G> {
+    /// Serialize the key in the version 1 encoding, i.e. the secret followed by the
+    /// encoded encrypt key, which keeps the existing serde format. The compact encoding
+    /// is available as [`DecryptKey::to_bytes_compact`].
+    fn serialize_with_mode<S>(
+        &self,
+        mode: SerializationMode,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         let mut bytes = Vec::new();
         self.secret
-            .serialize_compressed(&mut bytes)
+            .serialize_with_mode(&mut bytes, mode.into())
             .map_err(|_| serde::ser::Error::custom("Failed to serialize the secret"))?;
 
-        let enc_bytes = bincode::serialize(&self.encrypt_key).map_err(serde::ser::Error::custom)?;
+        let enc_bytes = match mode {
+            SerializationMode::Compressed => bincode::serialize(&self.encrypt_key),
+            SerializationMode::Uncompressed => bincode::serialize(&Uncompressed(self.encrypt_key)),
+        }
+        .map_err(serde::ser::Error::custom)?;
 
         bytes.extend(enc_bytes);
-        serializer.serialize_bytes(&bytes)
+        serializer.serialize_bytes(&format::frame_versioned::<G>(mode, format::KEY_V1, &bytes))
+    }
+}
+
+impl<G: CurveGroup> Serialize for DecryptKey<G> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.serialize_with_mode(SerializationMode::Compressed, serializer)
+    }
+}
+
+impl<G: CurveGroup> Serialize for Uncompressed<DecryptKey<G>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0
+            .serialize_with_mode(SerializationMode::Uncompressed, serializer)
     }
 }
 
 impl<'de, G: CurveGroup> Deserialize<'de> for DecryptKey<G> {
+    /// Deserialize either the version 1 encoding, i.e. the secret followed by the encoded
+    /// encrypt key, or the compact version 2 encoding, i.e. the generator followed by the
+    /// secret. The encrypt key of a version 1 encoding is checked against the secret with
+    /// [`DecryptKey::validate`], failing with [`Error::CorruptKey`] on a mismatch. A
+    /// version 2 encoding fails with [`Error::InvalidGenerator`] for the identity
+    /// generator, or [`Error::InvalidEncoding`] if bytes are left over, like
+    /// [`DecryptKey::from_bytes_compact`].
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         let bytes: Vec<u8> = Vec::deserialize(deserializer)?;
-        let secret = <G as PrimeGroup>::ScalarField::deserialize_compressed(&bytes[..])
-            .map_err(|_| serde::de::Error::custom("Failed to deserialize the secret"))?;
-        let secret_size = secret.serialized_size(ark_serialize::Compress::Yes);
-        let enc_key =
-            bincode::deserialize(&bytes[(secret_size)..]).map_err(serde::de::Error::custom)?;
-
-        Ok(DecryptKey {
-            secret,
-            encrypt_key: enc_key,
-        })
+        let (mode, version, mut bytes) =
+            format::unframe_versioned::<G>(&bytes).map_err(serde::de::Error::custom)?;
+        match version {
+            format::KEY_V1 => {
+                let secret = <G as PrimeGroup>::ScalarField::deserialize_with_mode(
+                    &mut bytes,
+                    mode.into(),
+                    Validate::Yes,
+                )
+                .map_err(|_| serde::de::Error::custom("Failed to deserialize the secret"))?;
+                let enc_key = bincode::deserialize(bytes).map_err(serde::de::Error::custom)?;
+
+                let key = DecryptKey {
+                    secret,
+                    encrypt_key: enc_key,
+                };
+                if !key.validate() {
+                    return Err(serde::de::Error::custom(Error::CorruptKey));
+                }
+                Ok(key)
+            }
+            format::KEY_V2 => {
+                let generator = G::deserialize_with_mode(&mut bytes, mode.into(), Validate::Yes)
+                    .map_err(|_| serde::de::Error::custom("Failed to deserialize the generator"))?;
+                let secret = <G as PrimeGroup>::ScalarField::deserialize_with_mode(
+                    &mut bytes,
+                    mode.into(),
+                    Validate::Yes,
+                )
+                .map_err(|_| serde::de::Error::custom("Failed to deserialize the secret"))?;
+                if !bytes.is_empty() {
+                    return Err(serde::de::Error::custom(Error::InvalidEncoding));
+                }
+                DecryptKey::try_new(generator.into_affine(), secret)
+                    .map_err(serde::de::Error::custom)
+            }
+            v => Err(serde::de::Error::custom(Error::UnsupportedVersion(v))),
+        }
     }
 }
@@ -0,0 +1,74 @@
+//! Encoding of byte strings as sequences of points, to encrypt data longer than a point
+//! with ElGamal alone, chunk by chunk.
+//!
+//! A point of G1 cannot carry bytes in its coordinates: the prime order subgroup is a
+//! tiny fraction of the curve, so almost no coordinate encoding of data lands in it.
+//! Instead, every chunk of [`CHUNK_SIZE`] bytes is the small integer v of its big-endian
+//! bytes, encoded as the point vP on the standard generator P like
+//! [`PublicKey::encrypt_scalar`](crate::PublicKey::encrypt_scalar), and decoded with a
+//! [`BsgsTable`] covering [`CHUNK_MAX`]. The chunks stay additively homomorphic.
+//!
+//! The first point encodes the length of the data, so the zero padding of the last chunk
+//! is removed on decoding.
+
+use super::{
+    bsgs::{encode_scalar, BsgsTable},
+    error::Error,
+    G1Affine, G1,
+};
+
+/// The number of bytes per chunk.
+pub const CHUNK_SIZE: usize = 3;
+
+/// The largest value of a chunk, and the longest data in bytes. A [`BsgsTable`] for
+/// [`decode_chunks`] must cover it.
+pub const CHUNK_MAX: u64 = (1 << (8 * CHUNK_SIZE)) - 1;
+
+/// Encode `msg` as its length followed by its chunks of [`CHUNK_SIZE`] bytes, the last
+/// one padded with zeros.
+///
+/// # Panics
+///
+/// Panics if `msg` is longer than [`CHUNK_MAX`] bytes.
+pub fn encode_chunks(msg: &[u8]) -> Vec<G1Affine> {
+    assert!(
+        msg.len() as u64 <= CHUNK_MAX,
+        "the data of {} bytes is longer than {CHUNK_MAX} bytes",
+        msg.len()
+    );
+    std::iter::once(msg.len() as u64)
+        .chain(msg.chunks(CHUNK_SIZE).map(|chunk| {
+            let mut bytes = [0u8; 8];
+            bytes[8 - CHUNK_SIZE..][..chunk.len()].copy_from_slice(chunk);
+            u64::from_be_bytes(bytes)
+        }))
+        .map(encode_scalar::<G1>)
+        .collect()
+}
+
+/// Decode the data encoded by [`encode_chunks`] as `points`, with the discrete logs of
+/// the chunks found in `table`.
+///
+/// Returns [`Error::OutOfRange`] if a point is not a chunk within the range of the table,
+/// or [`Error::InvalidEncoding`] if the length does not match the number of chunks or the
+/// padding is not zero.
+pub fn decode_chunks(points: &[G1Affine], table: &BsgsTable<G1>) -> Result<Vec<u8>, Error> {
+    let (len, chunks) = points.split_first().ok_or(Error::InvalidEncoding)?;
+    let len = table.discrete_log((*len).into())? as usize;
+    if chunks.len() != len.div_ceil(CHUNK_SIZE) {
+        return Err(Error::InvalidEncoding);
+    }
+    let mut msg = Vec::with_capacity(chunks.len() * CHUNK_SIZE);
+    for chunk in chunks {
+        let v = table.discrete_log((*chunk).into())?;
+        if v > CHUNK_MAX {
+            return Err(Error::OutOfRange);
+        }
+        msg.extend_from_slice(&v.to_be_bytes()[8 - CHUNK_SIZE..]);
+    }
+    if msg[len..].iter().any(|&b| b != 0) {
+        return Err(Error::InvalidEncoding);
+    }
+    msg.truncate(len);
+    Ok(msg)
+}
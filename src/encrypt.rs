@@ -1,7 +1,22 @@
-use ark_ec::{CurveGroup, PrimeGroup};
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_serialize::{CanonicalDeserialize, Validate};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
 use serde::{Deserialize, Serialize};
 
-use super::ciphertext::Ciphertext;
+use super::{
+    bsgs::{encode_scalar, encode_signed},
+    ciphertext::Ciphertext,
+    ephemeral::EphemeralEncryption,
+    error::Error,
+    format::{self, SerializationMode, Uncompressed},
+    rekey::ReKey,
+    scalar_mul::{ScalarMul, SoftwareScalarMul},
+    util::fr_from_random_bytes_ct,
+    Fr, G1Affine, G1,
+};
 
 /// A key to encrypt a message.
 ///
@@ -20,22 +35,239 @@ pub struct EncryptKey<G: CurveGroup> {
 impl<G: CurveGroup> EncryptKey<G> {
     /// Encrypt a message `m` with randomness `r`. Ciphertext is (rG, m + rY).
     pub fn encrypt(&self, m: G::Affine, r: <G as PrimeGroup>::ScalarField) -> Ciphertext<G> {
-        let a = self.generator * r;
-        let b = self.y * r + m;
+        self.encrypt_with(&SoftwareScalarMul, m, r)
+    }
+
+    /// Encrypt a message `m` with randomness `r` like [`EncryptKey::encrypt`], or return
+    /// [`Error::DegenerateKey`] if the key [is degenerate](EncryptKey::is_degenerate).
+    pub fn try_encrypt(
+        &self,
+        m: G::Affine,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Result<Ciphertext<G>, Error> {
+        if self.is_degenerate() {
+            return Err(Error::DegenerateKey);
+        }
+        Ok(self.encrypt(m, r))
+    }
+
+    /// Check if the public key y is the identity, i.e. the secret is zero. Ciphertexts
+    /// (rG, m + rY) under such a key are (rG, m), which reveal the message.
+    pub fn is_degenerate(&self) -> bool {
+        self.y.is_zero()
+    }
+
+    /// Encrypt a message `m` with randomness `r` like [`EncryptKey::encrypt`], computing
+    /// rG and rY with the scalar multiplication backend `backend`.
+    pub fn encrypt_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        m: G::Affine,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Ciphertext<G> {
+        count_ops!(SCALAR_MULS, 2);
+        let a = backend.mul(&self.generator, &r);
+        let b = backend.mul(&self.y, &r) + m;
         Ciphertext(a, b)
     }
 
+    /// Encrypt a message `m` given in projective form with randomness `r`, without
+    /// converting it to affine first. Ciphertext is (rG, m + rY).
+    pub fn encrypt_projective(&self, m: G, r: <G as PrimeGroup>::ScalarField) -> Ciphertext<G> {
+        self.encrypt_projective_with(&SoftwareScalarMul, m, r)
+    }
+
+    /// Encrypt a message `m` in projective form like [`EncryptKey::encrypt_projective`],
+    /// computing rG and rY with the scalar multiplication backend `backend`.
+    pub fn encrypt_projective_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        m: G,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Ciphertext<G> {
+        count_ops!(SCALAR_MULS, 2);
+        let a = backend.mul(&self.generator, &r);
+        let b = backend.mul(&self.y, &r) + m;
+        Ciphertext(a, b)
+    }
+
+    /// Encrypt the messages `messages` all with the same randomness `r`, computing rG and
+    /// rY once, so every message after the first costs a single addition.
+    ///
+    /// The ciphertexts share a = rG, and the difference of any two b components is the
+    /// difference of their messages, which anyone can compute. Use it only where that is
+    /// acceptable, e.g. for a commitment to a vector that is opened all at once, and
+    /// never for independent messages (see [`RandomnessGuard`](crate::RandomnessGuard)).
+    pub fn encrypt_reusing_ephemeral(
+        &self,
+        messages: &[G::Affine],
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Vec<Ciphertext<G>> {
+        count_ops!(SCALAR_MULS, 2);
+        let a = self.generator * r;
+        let ry = self.y * r;
+        messages.iter().map(|m| Ciphertext(a, ry + m)).collect()
+    }
+
+    /// Encrypt a random message with fresh randomness, e.g. to pad a batch to a fixed
+    /// size. The result is a real encryption, indistinguishable from the encryption of
+    /// any other message, and decrypts to a random point that the caller must filter.
+    pub fn dummy_ciphertext<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Ciphertext<G> {
+        self.encrypt_projective(G::rand(rng), <G as PrimeGroup>::ScalarField::rand(rng))
+    }
+
+    /// Encrypt a small integer `v` with randomness `r` (a.k.a. lifted ElGamal). The message
+    /// is the point vP, where P is the standard generator of the group rather than the
+    /// generator of this key, so that one [`BsgsTable`](crate::bsgs::BsgsTable) can
+    /// recover values encrypted under any key.
+    pub fn encrypt_scalar(&self, v: u64, r: <G as PrimeGroup>::ScalarField) -> Ciphertext<G> {
+        self.encrypt(encode_scalar::<G>(v), r)
+    }
+
+    /// Encrypt a small integer `v` with randomness `r` like [`EncryptKey::encrypt_scalar`],
+    /// or return [`Error::OutOfRange`] if `v` is larger than `max`. `max` should be the
+    /// maximum of the [`BsgsTable`](crate::bsgs::BsgsTable) the ciphertext is decrypted
+    /// with, so that values it cannot recover are rejected at encryption time.
+    pub fn encrypt_scalar_bounded(
+        &self,
+        v: u64,
+        max: u64,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Result<Ciphertext<G>, Error> {
+        if v > max {
+            return Err(Error::OutOfRange);
+        }
+        Ok(self.encrypt_scalar(v, r))
+    }
+
+    /// Encrypt a small signed integer `v` with randomness `r`, like
+    /// [`EncryptKey::encrypt_scalar`] but with negative values encoded as their field
+    /// negation. The integer can be recovered by
+    /// [`DecryptKey::decrypt_signed`](crate::decrypt::DecryptKey::decrypt_signed).
+    pub fn encrypt_signed(&self, v: i64, r: <G as PrimeGroup>::ScalarField) -> Ciphertext<G> {
+        self.encrypt(encode_signed::<G>(v), r)
+    }
+
     /// Rerandomize a ciphertext with randomness `r`. Ciphertext is (a + rG, b + rY).
     pub fn rerandomize(
         &self,
         ct: Ciphertext<G>,
         r: <G as PrimeGroup>::ScalarField,
     ) -> Ciphertext<G> {
-        let a = ct.0 + self.generator * r;
-        let b = ct.1 + self.y * r;
+        self.rerandomize_with(&SoftwareScalarMul, ct, r)
+    }
+
+    /// Rerandomize a ciphertext with randomness `r` like [`EncryptKey::rerandomize`],
+    /// computing rG and rY with the scalar multiplication backend `backend`.
+    pub fn rerandomize_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G>,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Ciphertext<G> {
+        count_ops!(SCALAR_MULS, 2);
+        let a = ct.0 + backend.mul(&self.generator, &r);
+        let b = ct.1 + backend.mul(&self.y, &r);
+        Ciphertext(a, b)
+    }
+
+    /// Refresh a stored ciphertext: rerandomize it with fresh randomness from `rng` and
+    /// normalize both points, so the result has the canonical (affine) representation.
+    pub fn refresh<R: CryptoRng + RngCore>(&self, ct: Ciphertext<G>, rng: &mut R) -> Ciphertext<G> {
+        let ct = self.rerandomize(ct, <G as PrimeGroup>::ScalarField::rand(rng));
+        count_ops!(NORMALIZATIONS);
+        let points = G::normalize_batch(&[ct.0, ct.1]);
+        Ciphertext(points[0].into(), points[1].into())
+    }
+
+    /// Encrypt a message `m` with a fresh one-time key pair (r, rG) sampled from `rng`,
+    /// returning the ephemeral public key rG and the payload m + rY.
+    pub fn encrypt_ephemeral<R: CryptoRng + RngCore>(
+        &self,
+        m: G::Affine,
+        rng: &mut R,
+    ) -> EphemeralEncryption<G> {
+        self.encrypt(m, <G as PrimeGroup>::ScalarField::rand(rng))
+            .into()
+    }
+
+    /// Re-encrypt a ciphertext (a, b) with the re-encryption key `rk` to this key and
+    /// rerandomize it with randomness `r` in one pass. Ciphertext is (a + rG, b + δa + rY),
+    /// where Y is the public key of this (the target) key.
+    pub fn rerandomize_and_rekey(
+        &self,
+        ct: Ciphertext<G>,
+        rk: &ReKey<G>,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Ciphertext<G> {
+        self.rerandomize_and_rekey_with(&SoftwareScalarMul, ct, rk, r)
+    }
+
+    /// Re-encrypt and rerandomize a ciphertext like [`EncryptKey::rerandomize_and_rekey`],
+    /// computing δa, rG and rY with the scalar multiplication backend `backend`.
+    pub fn rerandomize_and_rekey_with<M: ScalarMul<G>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G>,
+        rk: &ReKey<G>,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Ciphertext<G> {
+        count_ops!(SCALAR_MULS, 3);
+        let a = ct.0 + backend.mul(&self.generator, &r);
+        let b = ct.1 + backend.mul(&ct.0, &rk.delta) + backend.mul(&self.y, &r);
         Ciphertext(a, b)
     }
 
+    /// Combine the keys into an aggregate key Y = Y1 + ... + Yn for joint encryption. A
+    /// ciphertext under the aggregate key is decrypted with the sum of all secrets, see
+    /// [`DecryptKey::combine_secret`](crate::decrypt::DecryptKey::combine_secret).
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator.
+    pub fn combine(keys: &[EncryptKey<G>]) -> Result<Self, Error> {
+        let (first, rest) = keys.split_first().ok_or(Error::EmptyInput)?;
+        if rest.iter().any(|k| !k.same_generator(first)) {
+            return Err(Error::GeneratorMismatch);
+        }
+        Ok(EncryptKey {
+            generator: first.generator,
+            y: keys.iter().map(|k| k.y).sum(),
+        })
+    }
+
+    /// Create a key from the compressed generator `generator_bytes` and public key
+    /// `y_bytes`, checking that both are valid points of the prime order subgroup.
+    ///
+    /// Returns [`Error::InvalidEncoding`] if either is malformed, or
+    /// [`Error::InvalidGenerator`] if the generator is the identity.
+    pub fn from_compressed(generator_bytes: &[u8], y_bytes: &[u8]) -> Result<Self, Error> {
+        let generator = read_point::<G>(generator_bytes)?;
+        if generator.is_zero() {
+            return Err(Error::InvalidGenerator);
+        }
+        Ok(EncryptKey {
+            generator: generator.into_group(),
+            y: read_point::<G>(y_bytes)?.into_group(),
+        })
+    }
+
+    /// Encrypt a message `m` with randomness `r` to the key given by its compressed
+    /// generator and public key, see [`EncryptKey::from_compressed`].
+    pub fn encrypt_with_compressed(
+        generator_bytes: &[u8],
+        y_bytes: &[u8],
+        m: G::Affine,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Result<Ciphertext<G>, Error> {
+        Ok(Self::from_compressed(generator_bytes, y_bytes)?.encrypt(m, r))
+    }
+
+    /// Check whether this key and `other` share the same generator, which operations
+    /// across keys (e.g. [`EncryptKey::combine`]) require.
+    pub fn same_generator(&self, other: &EncryptKey<G>) -> bool {
+        self.generator == other.generator
+    }
+
     /// Get the generator.
     pub fn generator(&self) -> G::Affine {
         self.generator.into_affine()
@@ -47,19 +279,88 @@ impl<G: CurveGroup> EncryptKey<G> {
     }
 }
 
-impl<G: CurveGroup> Serialize for EncryptKey<G> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+/// Deserialize exactly one compressed point, validating it.
+fn read_point<G: CurveGroup>(bytes: &[u8]) -> Result<G::Affine, Error> {
+    let mut reader = bytes;
+    let point =
+        G::Affine::deserialize_compressed(&mut reader).map_err(|_| Error::InvalidEncoding)?;
+    if !reader.is_empty() {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok(point)
+}
+
+impl EncryptKey<G1> {
+    /// Encrypt a message `m` with the randomness r reduced from the 64 bytes
+    /// `rand_bytes` in constant time by [`reduce_bytes_ct`](crate::util::reduce_bytes_ct),
+    /// since r reveals the message. Returns the ciphertext and r, or
+    /// [`Error::InvalidRandomness`] if `rand_bytes` has any other length.
+    pub fn encrypt_from_randomness_bytes(
+        &self,
+        m: G1Affine,
+        rand_bytes: &[u8],
+    ) -> Result<(Ciphertext<G1>, Fr), Error> {
+        let r = fr_from_random_bytes_ct(rand_bytes)?;
+        Ok((self.encrypt(m, r), r))
+    }
+
+    /// Rerandomize a ciphertext with the randomness r reduced from the 64 bytes
+    /// `rand_bytes` in constant time, as in [`EncryptKey::encrypt_from_randomness_bytes`].
+    /// Returns [`Error::InvalidRandomness`] if `rand_bytes` has any other length.
+    pub fn rerandomize_from_bytes(
+        &self,
+        ct: Ciphertext<G1>,
+        rand_bytes: &[u8],
+    ) -> Result<Ciphertext<G1>, Error> {
+        Ok(self.rerandomize(ct, fr_from_random_bytes_ct(rand_bytes)?))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, G: CurveGroup> arbitrary::Arbitrary<'a> for EncryptKey<G> {
+    /// Generate the encryption key of an arbitrary [`DecryptKey`](crate::decrypt::DecryptKey).
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let dk: crate::decrypt::DecryptKey<G> = arbitrary::Arbitrary::arbitrary(u)?;
+        Ok(dk.encrypt_key)
+    }
+}
+
+impl<G: CurveGroup> EncryptKey<G> {
+    fn serialize_with_mode<S>(
+        &self,
+        mode: SerializationMode,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         let mut bytes = Vec::new();
         self.generator
-            .serialize_compressed(&mut bytes)
+            .serialize_with_mode(&mut bytes, mode.into())
             .map_err(|_| serde::ser::Error::custom("Failed to serialize the generator"))?;
         self.y
-            .serialize_compressed(&mut bytes)
+            .serialize_with_mode(&mut bytes, mode.into())
             .map_err(|_| serde::ser::Error::custom("Failed to serialize the public key"))?;
-        serializer.serialize_bytes(&bytes)
+        serializer.serialize_bytes(&format::frame_versioned::<G>(mode, format::KEY_V1, &bytes))
+    }
+}
+
+impl<G: CurveGroup> Serialize for EncryptKey<G> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.serialize_with_mode(SerializationMode::Compressed, serializer)
+    }
+}
+
+impl<G: CurveGroup> Serialize for Uncompressed<EncryptKey<G>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0
+            .serialize_with_mode(SerializationMode::Uncompressed, serializer)
     }
 }
 
@@ -69,10 +370,14 @@ impl<'de, G: CurveGroup> Deserialize<'de> for EncryptKey<G> {
         D: serde::Deserializer<'de>,
     {
         let bytes: Vec<u8> = Vec::deserialize(deserializer)?;
-        let generator = G::deserialize_compressed(&bytes[..])
+        let (mode, version, mut bytes) =
+            format::unframe_versioned::<G>(&bytes).map_err(serde::de::Error::custom)?;
+        if version != format::KEY_V1 {
+            return Err(serde::de::Error::custom(Error::UnsupportedVersion(version)));
+        }
+        let generator = G::deserialize_with_mode(&mut bytes, mode.into(), Validate::Yes)
             .map_err(|_| serde::de::Error::custom("Failed to deserialize the generator"))?;
-        let generator_size = generator.serialized_size(ark_serialize::Compress::Yes);
-        let y = G::deserialize_compressed(&bytes[generator_size..])
+        let y = G::deserialize_with_mode(&mut bytes, mode.into(), Validate::Yes)
             .map_err(|_| serde::de::Error::custom("Failed to deserialize the public key"))?;
         Ok(EncryptKey { generator, y })
     }
@@ -0,0 +1,105 @@
+use ark_ec::{CurveGroup, PrimeGroup};
+
+use super::{ciphertext::Ciphertext, error::Error};
+
+/// A vector of ciphertexts supporting element-wise homomorphic operations.
+///
+/// The binary operations require both vectors (or the weights) to have the same length,
+/// otherwise [`Error::LengthMismatch`] is returned.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EncryptedVec<G: CurveGroup> {
+    cts: Vec<Ciphertext<G>>,
+}
+
+impl<G: CurveGroup> EncryptedVec<G> {
+    /// Create an encrypted vector from its ciphertexts.
+    pub fn new(cts: Vec<Ciphertext<G>>) -> Self {
+        Self { cts }
+    }
+
+    /// Add two encrypted vectors element-wise, i.e. an encryption of the sum of the
+    /// plaintext vectors.
+    pub fn add(&self, other: &Self) -> Result<Self, Error> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    /// Subtract `other` from this encrypted vector element-wise.
+    pub fn sub(&self, other: &Self) -> Result<Self, Error> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    /// Multiply every element by the scalar `k`, i.e. an encryption of k times each
+    /// plaintext.
+    pub fn scalar_mul(&self, k: <G as PrimeGroup>::ScalarField) -> Self {
+        Self::new(self.cts.iter().map(|ct| ct * k).collect())
+    }
+
+    /// Compute the weighted sum of the elements with the public `weights`, i.e. an
+    /// encryption of the inner product of the plaintext vector and the weights. Both
+    /// components are computed with a multi-scalar multiplication.
+    pub fn dot_plaintext(
+        &self,
+        weights: &[<G as PrimeGroup>::ScalarField],
+    ) -> Result<Ciphertext<G>, Error> {
+        if weights.len() != self.cts.len() {
+            return Err(Error::LengthMismatch);
+        }
+        count_ops!(NORMALIZATIONS, 2);
+        count_ops!(SCALAR_MULS, 2 * weights.len());
+        let a = G::normalize_batch(&self.cts.iter().map(|ct| ct.0).collect::<Vec<_>>());
+        let b = G::normalize_batch(&self.cts.iter().map(|ct| ct.1).collect::<Vec<_>>());
+        Ok(Ciphertext(
+            G::msm_unchecked(&a, weights),
+            G::msm_unchecked(&b, weights),
+        ))
+    }
+
+    /// Get the number of elements.
+    pub fn len(&self) -> usize {
+        self.cts.len()
+    }
+
+    /// Check if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.cts.is_empty()
+    }
+
+    /// Get the ciphertexts.
+    pub fn as_slice(&self) -> &[Ciphertext<G>] {
+        &self.cts
+    }
+
+    /// Get the ciphertexts, consuming the vector.
+    pub fn into_inner(self) -> Vec<Ciphertext<G>> {
+        self.cts
+    }
+
+    fn zip_with(
+        &self,
+        other: &Self,
+        f: impl Fn(&Ciphertext<G>, &Ciphertext<G>) -> Ciphertext<G>,
+    ) -> Result<Self, Error> {
+        if other.cts.len() != self.cts.len() {
+            return Err(Error::LengthMismatch);
+        }
+        Ok(Self::new(
+            self.cts
+                .iter()
+                .zip(&other.cts)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        ))
+    }
+}
+
+impl<G: CurveGroup> From<Vec<Ciphertext<G>>> for EncryptedVec<G> {
+    fn from(cts: Vec<Ciphertext<G>>) -> Self {
+        Self::new(cts)
+    }
+}
+
+impl<G: CurveGroup> FromIterator<Ciphertext<G>> for EncryptedVec<G> {
+    fn from_iter<I: IntoIterator<Item = Ciphertext<G>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
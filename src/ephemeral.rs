@@ -0,0 +1,37 @@
+//! Encryption with the randomness r as a one-time key pair, in the style of ECIES or
+//! libsodium's sealed boxes.
+
+use ark_ec::CurveGroup;
+
+use super::ciphertext::Ciphertext;
+
+/// A ciphertext with its components named after the one-time key pair of the sender.
+///
+/// The sender samples a fresh secret r for every message and publishes the ephemeral
+/// public key rG. The shared secret rY = x(rG) is known to the sender and the recipient
+/// alone, and the payload is the message masked with it, m + rY. This is exactly the
+/// ciphertext (rG, m + rY), so the two convert into each other for free.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EphemeralEncryption<G: CurveGroup> {
+    /// The ephemeral public key rG.
+    pub ephemeral_pub: G::Affine,
+    /// The message masked with the shared secret, m + rY.
+    pub payload: G::Affine,
+}
+
+impl<G: CurveGroup> From<Ciphertext<G>> for EphemeralEncryption<G> {
+    fn from(ct: Ciphertext<G>) -> Self {
+        count_ops!(NORMALIZATIONS);
+        let points = G::normalize_batch(&[ct.0, ct.1]);
+        Self {
+            ephemeral_pub: points[0],
+            payload: points[1],
+        }
+    }
+}
+
+impl<G: CurveGroup> From<EphemeralEncryption<G>> for Ciphertext<G> {
+    fn from(enc: EphemeralEncryption<G>) -> Self {
+        Ciphertext(enc.ephemeral_pub.into(), enc.payload.into())
+    }
+}
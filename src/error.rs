@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Errors returned by the fallible operations of this crate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The discrete logarithm of a decrypted point is not within the range of the table.
+    OutOfRange,
+    /// The requested range cannot be searched within the memory budget.
+    TableTooLarge,
+    /// The keys do not share the same group generator.
+    GeneratorMismatch,
+    /// The bytes do not encode a valid point or scalar.
+    InvalidEncoding,
+    /// The group generator is the identity.
+    InvalidGenerator,
+    /// The operands have different lengths.
+    LengthMismatch,
+    /// The bytes were encoded for a different curve or group.
+    UnexpectedCurve,
+    /// The input has no values where at least one is required.
+    EmptyInput,
+    /// The randomness bytes do not have the required length.
+    InvalidRandomness,
+    /// The bytes do not have the exact length of the encoding.
+    InvalidLength { expected: usize, got: usize },
+    /// The encoding has a version this crate cannot read.
+    UnsupportedVersion(u8),
+    /// The public key embedded in a secret key encoding does not match the secret, e.g.
+    /// after a bit-flip.
+    CorruptKey,
+    /// The output buffer is shorter than the value written to it.
+    BufferTooSmall { needed: usize, got: usize },
+    /// The authenticated ciphertext fails to verify under the key, i.e. it is not
+    /// addressed to the key or was tampered with.
+    AuthenticationFailed,
+    /// The ciphertext reuses the randomness of a ciphertext seen before.
+    ReusedRandomness,
+    /// The public key is the identity, i.e. the secret is zero, so ciphertexts under it
+    /// reveal their messages.
+    DegenerateKey,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OutOfRange => write!(f, "value is out of the searchable range"),
+            Error::TableTooLarge => write!(f, "range is infeasible within the memory budget"),
+            Error::GeneratorMismatch => write!(f, "keys have different generators"),
+            Error::InvalidEncoding => write!(f, "bytes do not encode a valid point or scalar"),
+            Error::InvalidGenerator => write!(f, "generator is the identity"),
+            Error::LengthMismatch => write!(f, "operands have different lengths"),
+            Error::UnexpectedCurve => write!(f, "bytes were encoded for a different curve"),
+            Error::EmptyInput => write!(f, "input is empty"),
+            Error::InvalidRandomness => write!(f, "randomness bytes have the wrong length"),
+            Error::InvalidLength { expected, got } => {
+                write!(f, "expected {expected} bytes, got {got}")
+            }
+            Error::UnsupportedVersion(v) => write!(f, "unsupported encoding version {v}"),
+            Error::CorruptKey => write!(f, "embedded public key does not match the secret"),
+            Error::BufferTooSmall { needed, got } => {
+                write!(f, "buffer of {got} bytes is too small for {needed} bytes")
+            }
+            Error::AuthenticationFailed => write!(f, "ciphertext fails authentication"),
+            Error::ReusedRandomness => write!(f, "ciphertext reuses encryption randomness"),
+            Error::DegenerateKey => write!(f, "public key is the identity"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
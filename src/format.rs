@@ -0,0 +1,121 @@
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalSerialize, Compress};
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+
+use super::error::Error;
+
+/// The length of the curve identifier that prefixes every serde encoding.
+pub(crate) const CURVE_ID_SIZE: usize = 4;
+
+/// The version byte of the key encodings that are a secret followed by the encoding of
+/// the public key, and of the public key encodings.
+pub(crate) const KEY_V1: u8 = 1;
+/// The version byte of the compact secret key encodings, the generator followed by the
+/// secret, with the public key derived again on deserialization.
+pub(crate) const KEY_V2: u8 = 2;
+
+/// The form of the points (and scalars) in a serde encoding.
+///
+/// The serde impls of the keys and ciphertexts use [`SerializationMode::Compressed`]. To
+/// serialize in [`SerializationMode::Uncompressed`] form, wrap the value in
+/// [`Uncompressed`]. Deserialization accepts either form, since the mode is recorded in
+/// the curve identifier that prefixes the encoding.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SerializationMode {
+    /// Points are compressed to their x-coordinate and a flag, the smallest encoding (48
+    /// bytes per G1 point), but loading them requires a square root per point.
+    #[default]
+    Compressed,
+    /// Points are stored with both coordinates (96 bytes per G1 point), so loading them
+    /// needs no decompression.
+    Uncompressed,
+}
+
+impl From<SerializationMode> for Compress {
+    fn from(mode: SerializationMode) -> Self {
+        match mode {
+            SerializationMode::Compressed => Compress::Yes,
+            SerializationMode::Uncompressed => Compress::No,
+        }
+    }
+}
+
+/// A wrapper to serialize a key or ciphertext with serde in
+/// [`SerializationMode::Uncompressed`] form, e.g. `bincode::serialize(&Uncompressed(ct))`.
+///
+/// It deserializes either form, like the wrapped type itself.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Uncompressed<T>(pub T);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Uncompressed<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Uncompressed)
+    }
+}
+
+/// Identify the curve of the group `G` and the serialization mode by the first bytes of
+/// the SHA-256 hash of its standard generator serialized in that mode, so that bytes
+/// encoded for another curve (or another group of the same curve) are rejected rather
+/// than misread.
+pub(crate) fn curve_id<G: CurveGroup>(mode: SerializationMode) -> [u8; CURVE_ID_SIZE] {
+    let mut bytes = Vec::new();
+    G::generator()
+        .into_affine()
+        .serialize_with_mode(&mut bytes, mode.into())
+        .expect("serializing to a vector cannot fail");
+    let hash = Sha256::digest(&bytes);
+    let mut id = [0u8; CURVE_ID_SIZE];
+    id.copy_from_slice(&hash[..CURVE_ID_SIZE]);
+    id
+}
+
+/// Prefix an encoding of values over the group `G` in the mode `mode` with the curve
+/// identifier.
+pub(crate) fn frame<G: CurveGroup>(mode: SerializationMode, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(CURVE_ID_SIZE + payload.len());
+    bytes.extend_from_slice(&curve_id::<G>(mode));
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+/// Prefix a key encoding of the version `version` with the version byte, and then with
+/// the curve identifier as in [`frame`].
+pub(crate) fn frame_versioned<G: CurveGroup>(
+    mode: SerializationMode,
+    version: u8,
+    payload: &[u8],
+) -> Vec<u8> {
+    frame::<G>(mode, &[&[version], payload].concat())
+}
+
+/// Strip the curve identifier and the version byte of a key encoding framed by
+/// [`frame_versioned`], returning the mode, the version and the payload. Returns the
+/// errors of [`unframe`], or [`Error::InvalidEncoding`] if there is no version byte.
+pub(crate) fn unframe_versioned<G: CurveGroup>(
+    bytes: &[u8],
+) -> Result<(SerializationMode, u8, &[u8]), Error> {
+    let (mode, payload) = unframe::<G>(bytes)?;
+    let (&version, payload) = payload.split_first().ok_or(Error::InvalidEncoding)?;
+    Ok((mode, version, payload))
+}
+
+/// Strip the curve identifier of an encoding framed by [`frame`], returning the mode of
+/// the encoding and the payload, or return [`Error::UnexpectedCurve`] if it does not
+/// identify the group `G`.
+pub(crate) fn unframe<G: CurveGroup>(bytes: &[u8]) -> Result<(SerializationMode, &[u8]), Error> {
+    let (id, payload) = bytes
+        .split_at_checked(CURVE_ID_SIZE)
+        .ok_or(Error::UnexpectedCurve)?;
+    [
+        SerializationMode::Compressed,
+        SerializationMode::Uncompressed,
+    ]
+    .into_iter()
+    .find(|&mode| id == curve_id::<G>(mode))
+    .map(|mode| (mode, payload))
+    .ok_or(Error::UnexpectedCurve)
+}
@@ -0,0 +1,50 @@
+//! Named generators of G1, so that every party picks the same base for keys and
+//! commitments instead of each sampling its own.
+
+use ark_ec::{
+    hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+    short_weierstrass::SWCurveConfig,
+};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use sha2::Sha256;
+
+use super::{G1Affine, G1};
+
+/// The domain separation tag of [`nums`].
+const NUMS_DST: &[u8] = b"BLS_ELGAMAL_NUMS_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+/// The domain separation tag of [`label_point`].
+const LABEL_DST: &[u8] = b"BLS_ELGAMAL_LABEL_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// The standard generator of G1, i.e. the canonical generator of arkworks and of the
+/// BLS12-381 specification. It is the generator of [`SecretKey::from_scalar`](crate::SecretKey::from_scalar).
+pub const G1_STANDARD: G1Affine = ark_bls12_381::g1::Config::GENERATOR;
+
+/// Hash the domain string `dst` to a point of G1 whose discrete log with respect to
+/// any other generator is unknown (a nothing-up-my-sleeve generator), with the
+/// `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite of RFC 9380.
+///
+/// This is the recommended way to obtain an independent base H, e.g. for twisted
+/// ElGamal or Pedersen commitments: a randomly sampled H = hG comes with the risk that
+/// h is known to whoever sampled it, while every party can recompute this point from
+/// `dst` and check that no trapdoor was involved.
+pub fn nums(dst: &[u8]) -> G1Affine {
+    hash_to_g1(NUMS_DST, dst)
+}
+
+/// Hash the context label `label` to the point H(label) that
+/// [`PublicKey::encrypt_with_label`](crate::PublicKey::encrypt_with_label) adds to the
+/// message, with the same suite as [`nums`] under a separate domain.
+pub fn label_point(label: &[u8]) -> G1Affine {
+    hash_to_g1(LABEL_DST, label)
+}
+
+fn hash_to_g1(dst: &[u8], msg: &[u8]) -> G1Affine {
+    type Hasher = MapToCurveBasedHasher<
+        G1,
+        DefaultFieldHasher<Sha256, 128>,
+        WBMap<ark_bls12_381::g1::Config>,
+    >;
+    Hasher::new(dst)
+        .and_then(|hasher| hasher.hash(msg))
+        .expect("hashing to G1 cannot fail")
+}
@@ -0,0 +1,309 @@
+//! Newtypes bridging the BLS12-381 types to the `ff` and `group` traits (enabled by the
+//! `group-compat` feature), for code written against those traits rather than arkworks.
+//!
+//! [`Scalar`] wraps [`Fr`] and implements [`ff::Field`] and [`ff::PrimeField`], and
+//! [`Point`] wraps [`G1`] and implements [`group::Group`]. Both convert to and from the
+//! wrapped types with [`From`], so values can be passed back to the rest of this crate:
+//!
+//! ```rust
+//! use ark_ec::AffineRepr;
+//! use ark_std::UniformRand;
+//! use bls_elgamal::{group_compat::{Point, Scalar}, Fr, G1Affine, SecretKey, G1};
+//! use ff::Field;
+//! use group::Group;
+//!
+//! // e.g. a message computed by generic code over `group::Group`
+//! fn double_generator<P: Group>() -> P {
+//!     P::generator().double()
+//! }
+//!
+//! let rng = &mut rand::thread_rng();
+//! let sk = SecretKey::new(G1Affine::generator(), Fr::rand(rng));
+//!
+//! let m: G1 = double_generator::<Point>().into();
+//! let r: Fr = Scalar::random(&mut *rng).into();
+//! let ct = sk.public_key().encrypt_projective(m, r);
+//! assert_eq!(G1::from(sk.decrypt(ct)), m);
+//! ```
+//!
+//! # Performance
+//!
+//! The newtypes are `#[repr(transparent)]`, so converting between them and the arkworks
+//! types is free, and the group and field arithmetic is the arkworks arithmetic. The
+//! overhead lies in the methods the traits require to return [`subtle`] types:
+//!
+//! - [`Field::invert`], [`Field::sqrt`] and [`PrimeField::from_repr`] call the arkworks
+//!   routines, which are not constant time, and then wrap the result in a [`CtOption`].
+//! - [`PrimeField::to_repr`] and [`PrimeField::is_odd`] convert out of Montgomery form,
+//!   costing a field multiplication each.
+//! - [`Point`] equality and [`Group::is_identity`] compare projective coordinates, as in
+//!   arkworks, without the constant-time guarantee the `group` traits suggest.
+
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use ark_ff::{AdditiveGroup, BigInt, BigInteger, Field as _, MontFp, PrimeField as _, Zero};
+use ark_std::{rand::RngCore, UniformRand};
+use ff::{Field, PrimeField};
+use group::Group;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+// the same types as `crate::{Fr, G1}`, named directly because trait coherence does not see
+// through the associated type projections of those aliases
+use ark_bls12_381::{Fr, G1Projective as G1};
+
+/// A scalar of BLS12-381, implementing [`ff::Field`] and [`ff::PrimeField`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Scalar(pub Fr);
+
+/// A point of the BLS12-381 G1 group, implementing [`group::Group`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Point(pub G1);
+
+impl From<Fr> for Scalar {
+    fn from(s: Fr) -> Self {
+        Scalar(s)
+    }
+}
+
+impl From<Scalar> for Fr {
+    fn from(s: Scalar) -> Self {
+        s.0
+    }
+}
+
+impl From<G1> for Point {
+    fn from(p: G1) -> Self {
+        Point(p)
+    }
+}
+
+impl From<Point> for G1 {
+    fn from(p: Point) -> Self {
+        p.0
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(v: u64) -> Self {
+        Scalar(Fr::from(v))
+    }
+}
+
+// Implement the arithmetic operators, for both owned and borrowed right-hand sides
+
+macro_rules! impl_binary_ops {
+    ($t:ident, $rhs:ident, $op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl $op<$rhs> for $t {
+            type Output = $t;
+
+            fn $op_fn(self, rhs: $rhs) -> $t {
+                $t($op::$op_fn(self.0, rhs.0))
+            }
+        }
+
+        impl<'a> $op<&'a $rhs> for $t {
+            type Output = $t;
+
+            fn $op_fn(self, rhs: &'a $rhs) -> $t {
+                $t($op::$op_fn(self.0, rhs.0))
+            }
+        }
+
+        impl $assign<$rhs> for $t {
+            fn $assign_fn(&mut self, rhs: $rhs) {
+                $assign::$assign_fn(&mut self.0, rhs.0);
+            }
+        }
+
+        impl<'a> $assign<&'a $rhs> for $t {
+            fn $assign_fn(&mut self, rhs: &'a $rhs) {
+                $assign::$assign_fn(&mut self.0, rhs.0);
+            }
+        }
+    };
+}
+
+impl_binary_ops!(Scalar, Scalar, Add, add, AddAssign, add_assign);
+impl_binary_ops!(Scalar, Scalar, Sub, sub, SubAssign, sub_assign);
+impl_binary_ops!(Scalar, Scalar, Mul, mul, MulAssign, mul_assign);
+impl_binary_ops!(Point, Point, Add, add, AddAssign, add_assign);
+impl_binary_ops!(Point, Point, Sub, sub, SubAssign, sub_assign);
+impl_binary_ops!(Point, Scalar, Mul, mul, MulAssign, mul_assign);
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        Scalar(-self.0)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(-self.0)
+    }
+}
+
+impl Sum for Scalar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Scalar::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Scalar> for Scalar {
+    fn sum<I: Iterator<Item = &'a Scalar>>(iter: I) -> Self {
+        iter.fold(Scalar::ZERO, Add::add)
+    }
+}
+
+impl Product for Scalar {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Scalar::ONE, Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a Scalar> for Scalar {
+    fn product<I: Iterator<Item = &'a Scalar>>(iter: I) -> Self {
+        iter.fold(Scalar::ONE, Mul::mul)
+    }
+}
+
+impl Sum for Point {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Point::identity(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Point> for Point {
+    fn sum<I: Iterator<Item = &'a Point>>(iter: I) -> Self {
+        iter.fold(Point::identity(), Add::add)
+    }
+}
+
+// Implement the constant-time traits on the Montgomery limbs, which are always reduced
+
+impl ConditionallySelectable for Scalar {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let (a, b) = ((a.0).0 .0, (b.0).0 .0);
+        let limbs = std::array::from_fn(|i| u64::conditional_select(&a[i], &b[i], choice));
+        Scalar(Fr::new_unchecked(BigInt(limbs)))
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (self.0).0 .0.ct_eq(&(other.0).0 .0)
+    }
+}
+
+impl Field for Scalar {
+    const ZERO: Self = Scalar(Fr::ZERO);
+    const ONE: Self = Scalar(Fr::ONE);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Scalar(Fr::rand(&mut rng))
+    }
+
+    fn square(&self) -> Self {
+        Scalar(self.0.square())
+    }
+
+    fn double(&self) -> Self {
+        Scalar(AdditiveGroup::double(&self.0))
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        let inverse = self.0.inverse();
+        CtOption::new(
+            Scalar(inverse.unwrap_or_default()),
+            Choice::from(inverse.is_some() as u8),
+        )
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        let root = self.0.sqrt();
+        CtOption::new(
+            Scalar(root.unwrap_or_default()),
+            Choice::from(root.is_some() as u8),
+        )
+    }
+}
+
+impl PrimeField for Scalar {
+    /// The canonical little-endian encoding, the same as the compressed arkworks
+    /// serialization.
+    type Repr = [u8; 32];
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let limbs = std::array::from_fn(|i| {
+            u64::from_le_bytes(repr[i * 8..(i + 1) * 8].try_into().unwrap())
+        });
+        let s = Fr::from_bigint(BigInt(limbs));
+        CtOption::new(
+            Scalar(s.unwrap_or_default()),
+            Choice::from(s.is_some() as u8),
+        )
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        let mut repr = [0u8; 32];
+        repr.copy_from_slice(&self.0.into_bigint().to_bytes_le());
+        repr
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.0.into_bigint().is_odd() as u8)
+    }
+
+    const MODULUS: &'static str =
+        "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    const NUM_BITS: u32 = 255;
+    const CAPACITY: u32 = 254;
+    const TWO_INV: Self = Scalar(MontFp!(
+        "26217937587563095239723870254092982918845276250263818911301829349969290592257"
+    ));
+    const MULTIPLICATIVE_GENERATOR: Self = Scalar(MontFp!("7"));
+    const S: u32 = 32;
+    const ROOT_OF_UNITY: Self = Scalar(MontFp!(
+        "10238227357739495823651030575849232062558860180284477541189508159991286009131"
+    ));
+    const ROOT_OF_UNITY_INV: Self = Scalar(MontFp!(
+        "2361660012179324004544952053205906600198439069793331036989202475535180077688"
+    ));
+    const DELTA: Self = Scalar(MontFp!(
+        "3793952369011177517951424454785176000433849974408744014172535497121832470999"
+    ));
+}
+
+impl Group for Point {
+    type Scalar = Scalar;
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Point(G1::rand(&mut rng))
+    }
+
+    fn identity() -> Self {
+        Point(G1::zero())
+    }
+
+    fn generator() -> Self {
+        Point(<G1 as ark_ec::PrimeGroup>::generator())
+    }
+
+    fn is_identity(&self) -> Choice {
+        Choice::from(self.0.is_zero() as u8)
+    }
+
+    fn double(&self) -> Self {
+        Point(AdditiveGroup::double(&self.0))
+    }
+}
@@ -0,0 +1,57 @@
+//! Detection of reused encryption randomness on ingest.
+//!
+//! Two ciphertexts (rG, m1 + rY) and (rG, m2 + rY) with the same randomness r under one
+//! key leak the difference m1 - m2 of their messages. The randomness is hidden, but it
+//! is reused exactly when the components a = rG repeat, which [`RandomnessGuard`] tracks.
+
+use std::collections::HashSet;
+
+use ark_ec::CurveGroup;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+use super::{ciphertext::Ciphertext, error::Error};
+
+/// A record of the components a = rG of the ciphertexts seen so far, stored as SHA-256
+/// hashes so the memory per ciphertext is fixed.
+///
+/// It is meant for ciphertexts to a single key (or keys sharing a generator), e.g. on a
+/// server storing them; ciphertexts under keys with different generators may repeat a
+/// component without sharing randomness.
+#[derive(Clone, Debug, Default)]
+pub struct RandomnessGuard {
+    seen: HashSet<[u8; 32]>,
+}
+
+impl RandomnessGuard {
+    /// Create a guard that has seen no ciphertexts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the component a of `ct`, or return [`Error::ReusedRandomness`] if a
+    /// ciphertext with the same component was seen before. A rejected ciphertext is not
+    /// recorded again.
+    pub fn check<G: CurveGroup>(&mut self, ct: &Ciphertext<G>) -> Result<(), Error> {
+        count_ops!(NORMALIZATIONS);
+        let mut bytes = Vec::new();
+        ct.0.into_affine()
+            .serialize_compressed(&mut bytes)
+            .expect("serializing to a vector cannot fail");
+        if self.seen.insert(Sha256::digest(bytes).into()) {
+            Ok(())
+        } else {
+            Err(Error::ReusedRandomness)
+        }
+    }
+
+    /// Get the number of distinct components seen.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Check if no ciphertexts were seen.
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
@@ -0,0 +1,49 @@
+//! JSON Web Key (RFC 7517) encoding of public keys, for JOSE-based systems.
+//!
+//! The key is an octet key pair on the curve `BLS12381G1`, as in the IETF draft on BLS
+//! key representations: `x` is the base64url encoded compressed point y. Since keys of
+//! this crate may use any generator, the compressed generator is the extra member `g`.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::{json, Value};
+
+use super::{Error, PublicKey};
+
+/// The key type of the JWK.
+const KTY: &str = "OKP";
+/// The curve of the JWK.
+const CRV: &str = "BLS12381G1";
+
+impl PublicKey {
+    /// Export the key as a JSON Web Key with `kty` `OKP`, `crv` `BLS12381G1`, and the
+    /// compressed y and generator as the base64url (unpadded) members `x` and `g`.
+    pub fn to_jwk(&self) -> Value {
+        let bytes = self.to_bytes();
+        let (g, y) = bytes.split_at(bytes.len() / 2);
+        json!({
+            "kty": KTY,
+            "crv": CRV,
+            "x": URL_SAFE_NO_PAD.encode(y),
+            "g": URL_SAFE_NO_PAD.encode(g),
+        })
+    }
+
+    /// Import a key exported by [`PublicKey::to_jwk`]. Returns [`Error::InvalidEncoding`]
+    /// if `kty` or `crv` does not match, a member is missing, not base64url or not a
+    /// compressed point, or [`Error::InvalidGenerator`] if the generator is the identity.
+    pub fn from_jwk(jwk: &Value) -> Result<Self, Error> {
+        if jwk["kty"] != KTY || jwk["crv"] != CRV {
+            return Err(Error::InvalidEncoding);
+        }
+        let point = |name: &str| {
+            jwk[name]
+                .as_str()
+                .and_then(|s| URL_SAFE_NO_PAD.decode(s).ok())
+                .filter(|bytes| bytes.len() == PublicKey::SERIALIZED_SIZE / 2)
+                .ok_or(Error::InvalidEncoding)
+        };
+        let mut bytes = point("g")?;
+        bytes.extend(point("x")?);
+        PublicKey::try_from(bytes)
+    }
+}
@@ -1,37 +1,177 @@
 #![doc = include_str!("../README.md")]
 
+/// Count `n` (default 1) operations of the kind `op` (a counter of `metrics`); it
+/// compiles to nothing without the `metrics` feature.
+macro_rules! count_ops {
+    ($op:ident) => {
+        count_ops!($op, 1)
+    };
+    ($op:ident, $n:expr) => {
+        #[cfg(feature = "metrics")]
+        $crate::metrics::record(&$crate::metrics::$op, $n as u64);
+    };
+}
+
+#[cfg(feature = "bech32")]
+mod address;
+
+pub mod bit;
+pub use bit::EncryptedBit;
+
+pub mod aggregation;
+pub use aggregation::{AggProof, AggregationTree};
+
+pub mod bsgs;
+pub use bsgs::BsgsTable;
+
+mod bytes;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
 pub mod ciphertext;
-pub use ciphertext::Ciphertext;
+pub use ciphertext::{Ciphertext, CiphertextRef};
 
 pub mod decrypt;
 pub use decrypt::DecryptKey;
 
+pub mod encoding;
+
 pub mod encrypt;
 pub use encrypt::EncryptKey;
 
+pub mod encrypted_vec;
+pub use encrypted_vec::EncryptedVec;
+
+pub mod ephemeral;
+pub use ephemeral::EphemeralEncryption;
+
+#[cfg(feature = "constant_time")]
+mod constant_time;
+
+pub mod error;
+pub use error::Error;
+
+mod format;
+pub use format::{SerializationMode, Uncompressed};
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+pub mod multi_ciphertext;
+pub use multi_ciphertext::MultiCiphertext;
+
+pub mod generators;
+
+#[cfg(feature = "group-compat")]
+pub mod group_compat;
+
+pub mod guard;
+pub use guard::RandomnessGuard;
+
+#[cfg(feature = "jwk")]
+mod jwk;
+
+pub mod pairing;
+
+pub mod pedersen;
+pub use pedersen::PedersenParams;
+
+pub mod prepared;
+pub use prepared::PreparedDecryptKey;
+
+pub mod proof;
+pub use proof::{
+    BatchDecryptionProof, MatchProof, OneOfManyProof, OrProof, SameSecretProof, SigmaProof,
+    ZeroProof,
+};
+
+pub mod rekey;
+pub use rekey::ReKey;
+
+pub mod relation;
+
+pub mod scalar_mul;
+pub use scalar_mul::{ScalarMul, SoftwareScalarMul};
+
+#[cfg(feature = "seal")]
+pub mod seal;
+
+pub mod sigma;
+pub use sigma::{DleqStatement, SigmaCommitment, SigmaProver, SigmaResponse};
+
+pub mod tally;
+pub use tally::{BallotTally, Tally};
+
+pub mod util;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup};
-use serde::{Deserialize, Serialize};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use sha2::Sha256;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
 // re-export the curve types
 pub type G1 = <ark_bls12_381::Bls12_381 as Pairing>::G1;
 pub type G1Affine = <G1 as CurveGroup>::Affine;
 pub type Fr = <G1 as PrimeGroup>::ScalarField;
+pub type G2 = <ark_bls12_381::Bls12_381 as Pairing>::G2;
+pub type G2Affine = <G2 as CurveGroup>::Affine;
+
+/// Sample a scalar from fuzzer input. 64 bytes are reduced so that the result is
+/// (close to) uniform over the scalar field.
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_scalar<G: CurveGroup>(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<<G as PrimeGroup>::ScalarField> {
+    use ark_ff::PrimeField;
+
+    let bytes: [u8; 64] = u.arbitrary()?;
+    Ok(<G as PrimeGroup>::ScalarField::from_le_bytes_mod_order(
+        &bytes,
+    ))
+}
 
 /// A secret key for Elgamal encryption over the BLS12-381 curve, basically
 /// a wrapper around the [`DecryptKey`] struct.
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SecretKey {
     inner: DecryptKey<G1>,
 }
 
 impl SecretKey {
     /// Create a new secret key with group generator `g1` and secret `x`.
+    ///
+    /// The generator is not checked, see [`SecretKey::try_new`].
     pub fn new(g1: G1Affine, x: Fr) -> Self {
         Self {
             inner: DecryptKey::new(g1, x),
         }
     }
 
+    /// Create a new secret key with the standard BLS12-381 G1 generator
+    /// [`generators::G1_STANDARD`] and secret `x`, e.g. from a scalar of other
+    /// arkworks-based code. Use [`SecretKey::new`] for a custom generator.
+    pub fn from_scalar(x: Fr) -> Self {
+        Self::new(generators::G1_STANDARD, x)
+    }
+
+    /// Create a new secret key with group generator `g1` and secret `x`, or return
+    /// [`Error::InvalidGenerator`] if the generator is the identity.
+    pub fn try_new(g1: G1Affine, x: Fr) -> Result<Self, Error> {
+        Ok(Self {
+            inner: DecryptKey::try_new(g1, x)?,
+        })
+    }
+
     /// Decrypt a ciphertext `ct` to get the message.
     ///
     /// # Example
@@ -58,6 +198,398 @@ impl SecretKey {
         self.inner.decrypt(ct)
     }
 
+    /// Precompute the multiplication by the secret for fast decryption of ciphertexts
+    /// that arrive later, see [`PreparedDecryptKey`].
+    pub fn prepare(&self) -> PreparedDecryptKey {
+        self.inner.prepare()
+    }
+
+    /// Decrypt a ciphertext `ct` and write the compressed message (48 bytes) to the start
+    /// of `out`, e.g. a stack buffer in constrained environments. Returns the number of
+    /// bytes written, or [`Error::BufferTooSmall`] if `out` is shorter.
+    pub fn decrypt_into(&self, ct: Ciphertext<G1>, out: &mut [u8]) -> Result<usize, Error> {
+        self.inner.decrypt_into(ct, out)
+    }
+
+    /// Decrypt the ciphertexts `cts`, normalizing all the messages in one batch.
+    pub fn decrypt_batch(&self, cts: &[Ciphertext<G1>]) -> Vec<G1Affine> {
+        self.inner.decrypt_batch(cts)
+    }
+
+    /// Decrypt the ciphertexts `cts` in one batch like [`SecretKey::decrypt_batch`],
+    /// computing the scalar multiplications with the backend `backend`.
+    pub fn decrypt_batch_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        cts: &[Ciphertext<G1>],
+    ) -> Vec<G1Affine> {
+        self.inner.decrypt_batch_with(backend, cts)
+    }
+
+    /// Decrypt a ciphertext `ct` encrypted to an unknown one of the keys `keys` with each
+    /// of them, sharing the multiples of a, see [`DecryptKey::decrypt_try_keys`].
+    pub fn decrypt_try_keys(ct: Ciphertext<G1>, keys: &[SecretKey]) -> Vec<G1Affine> {
+        let keys = keys.iter().map(|k| k.inner).collect::<Vec<_>>();
+        DecryptKey::decrypt_try_keys(ct, &keys)
+    }
+
+    /// Decrypt a ciphertext `ct` like [`SecretKey::decrypt`], computing the scalar
+    /// multiplication with the backend `backend`, e.g. a hardware accelerator.
+    pub fn decrypt_with<M: ScalarMul<G1>>(&self, backend: &M, ct: Ciphertext<G1>) -> G1Affine {
+        self.inner.decrypt_with(backend, ct)
+    }
+
+    /// Decrypt a ciphertext `ct` from [`PublicKey::encrypt_with_label`] with the context
+    /// `label`, subtracting H(label) from the decrypted point.
+    ///
+    /// Any point is a valid message, so a wrong label cannot be detected here: it gives
+    /// the message shifted by the difference of the label points. To reject ciphertexts
+    /// of other contexts, encrypt messages with structure and check it after decryption,
+    /// e.g. small integers with [`SecretKey::decrypt_scalar_with_label`].
+    ///
+    /// Labels are public and the encryption is additively homomorphic, so the label does
+    /// not prevent reuse across contexts: anyone can move a ciphertext from label `a` to
+    /// label `b` by adding H(b) - H(a) to its second component, and it then decrypts
+    /// under `b` to the original message.
+    pub fn decrypt_with_label(&self, ct: Ciphertext<G1>, label: &[u8]) -> G1Affine {
+        count_ops!(NORMALIZATIONS);
+        (self.inner.decrypt_projective(ct) - generators::label_point(label)).into_affine()
+    }
+
+    /// Decrypt a ciphertext `ct` of a small integer `v`, encrypted by
+    /// [`PublicKey::encrypt_with_label`] as the message vG with the context `label`, and
+    /// recover `v` with the table `table`.
+    ///
+    /// Returns [`Error::OutOfRange`] if the decrypted point minus H(label) is not in the
+    /// range of the table, e.g. because the ciphertext was encrypted with another label.
+    /// As with [`SecretKey::decrypt_with_label`], this only catches accidental mix-ups,
+    /// since anyone can move a ciphertext to another label.
+    pub fn decrypt_scalar_with_label(
+        &self,
+        ct: Ciphertext<G1>,
+        label: &[u8],
+        table: &BsgsTable<G1>,
+    ) -> Result<u64, Error> {
+        table.discrete_log(self.inner.decrypt_projective(ct) - generators::label_point(label))
+    }
+
+    /// Decrypt a ciphertext `ct` to get the message in projective form, leaving out the
+    /// conversion to affine form of [`SecretKey::decrypt`]. The conversion is a field
+    /// inversion, whose timing may depend on the message, so flows that must not leak
+    /// the message (e.g. comparisons like `SecretKey::decrypt_eq`) should stay in
+    /// projective form, and the caller decides when to normalize.
+    pub fn decrypt_projective(&self, ct: Ciphertext<G1>) -> G1 {
+        self.inner.decrypt_projective(ct)
+    }
+
+    /// Decrypt a ciphertext `ct` in projective form like [`SecretKey::decrypt_projective`],
+    /// computing the scalar multiplication with the backend `backend`.
+    pub fn decrypt_projective_with<M: ScalarMul<G1>>(&self, backend: &M, ct: Ciphertext<G1>) -> G1 {
+        self.inner.decrypt_projective_with(backend, ct)
+    }
+
+    /// Decrypt the difference of the messages of `ct1` and `ct2`. It subtracts the
+    /// ciphertexts homomorphically and decrypts once, so it costs a single decryption.
+    pub fn decrypt_difference(&self, ct1: Ciphertext<G1>, ct2: Ciphertext<G1>) -> G1Affine {
+        self.inner.decrypt_difference(ct1, ct2)
+    }
+
+    /// Decrypt a ciphertext `ct` once and check whether the message is in the set `set`,
+    /// e.g. an allow-list of plaintexts. The decrypted message is normalized to affine
+    /// form, so it is found regardless of how the ciphertext was computed. The lookup is
+    /// not constant time; for a secret single candidate use `SecretKey::decrypt_eq`
+    /// (with the `constant_time` feature).
+    pub fn decrypt_in_set(&self, ct: Ciphertext<G1>, set: &HashSet<G1Affine>) -> bool {
+        self.inner.decrypt_in_set(ct, set)
+    }
+
+    /// Decrypt a ciphertext `ct` and check whether the message equals `expected`, in
+    /// constant time.
+    ///
+    /// This is meant for protocols where the outcome of the comparison is secret, e.g.
+    /// authentication flows. Comparing decrypted points with `==` may return early at
+    /// the first differing coordinate and leak, through timing, how close a guess was.
+    /// Here the decrypted point is compared in projective form without normalization,
+    /// using constant-time field element comparisons. The scalar multiplication of
+    /// decryption itself is performed by arkworks, which does not guarantee constant
+    /// time, so this does not protect the secret key against timing attacks.
+    #[cfg(feature = "constant_time")]
+    pub fn decrypt_eq(&self, ct: Ciphertext<G1>, expected: G1Affine) -> subtle::Choice {
+        constant_time::ct_eq_projective(&self.decrypt_projective(ct), &expected)
+    }
+
+    /// Decrypt a ciphertext `ct` of a small integer created by
+    /// [`PublicKey::encrypt_scalar`], recovering the integer with the table `table`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{BsgsTable, Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let x = Fr::rand(rng);
+    /// let g1 = G1Affine::rand(rng);
+    ///
+    /// let sk = SecretKey::new(g1, x);
+    /// let pk = sk.public_key();
+    ///
+    /// // recover values up to 2^16 with a table of at most 1 MiB
+    /// let table = BsgsTable::new(1 << 16, 1 << 20).unwrap();
+    ///
+    /// let ct = pk.encrypt_scalar(42, Fr::rand(rng));
+    /// assert_eq!(sk.decrypt_scalar(ct, &table), Ok(42));
+    /// ```
+    pub fn decrypt_scalar(&self, ct: Ciphertext<G1>, table: &BsgsTable<G1>) -> Result<u64, Error> {
+        self.inner.decrypt_scalar(ct, table)
+    }
+
+    /// Decrypt a ciphertext `ct` of a small integer like [`SecretKey::decrypt_scalar`],
+    /// computing the scalar multiplication with the backend `backend`.
+    pub fn decrypt_scalar_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G1>,
+        table: &BsgsTable<G1>,
+    ) -> Result<u64, Error> {
+        self.inner.decrypt_scalar_with(backend, ct, table)
+    }
+
+    /// Decrypt the data encrypted by [`PublicKey::encrypt_chunks`], finding the chunks in
+    /// the table `table`, which must cover [`encoding::CHUNK_MAX`]. Returns the errors of
+    /// [`encoding::decode_chunks`].
+    pub fn decrypt_chunks(
+        &self,
+        cts: &[Ciphertext<G1>],
+        table: &BsgsTable<G1>,
+    ) -> Result<Vec<u8>, Error> {
+        encoding::decode_chunks(&self.decrypt_batch(cts), table)
+    }
+
+    /// Compare the small integers encrypted in the ciphertexts `a` and `b` by decrypting
+    /// them with the table `table`, e.g. to sort encrypted data. Returns `None` if either
+    /// is out of the range of the table.
+    ///
+    /// Only the key holder can compare ciphertexts; there is deliberately no public
+    /// ordering of ciphertexts, since it would reveal information about the plaintexts.
+    pub fn cmp_ciphertexts(
+        &self,
+        a: Ciphertext<G1>,
+        b: Ciphertext<G1>,
+        table: &BsgsTable<G1>,
+    ) -> Option<Ordering> {
+        self.inner.cmp_ciphertexts(a, b, table)
+    }
+
+    /// Decrypt a ciphertext of a small signed integer `v` encrypted by
+    /// [`PublicKey::encrypt_signed`], with the precomputed table `table`. Values in
+    /// `[-max, max]` are recovered, where `max` is the maximum of the table, otherwise
+    /// [`Error::OutOfRange`] is returned. Negative values take up to twice as long to
+    /// recover as non-negative ones, since both halves of the range are searched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{BsgsTable, Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    /// let table = BsgsTable::new(1 << 16, 1 << 20).unwrap();
+    ///
+    /// let balance = pk.encrypt_signed(100, Fr::rand(rng)) + pk.encrypt_signed(-142, Fr::rand(rng));
+    /// assert_eq!(sk.decrypt_signed(balance, &table), Ok(-42));
+    /// ```
+    pub fn decrypt_signed(&self, ct: Ciphertext<G1>, table: &BsgsTable<G1>) -> Result<i64, Error> {
+        self.inner.decrypt_signed(ct, table)
+    }
+
+    /// Decrypt a ciphertext `ct` of a small signed integer like
+    /// [`SecretKey::decrypt_signed`], computing the scalar multiplication with the backend
+    /// `backend`.
+    pub fn decrypt_signed_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G1>,
+        table: &BsgsTable<G1>,
+    ) -> Result<i64, Error> {
+        self.inner.decrypt_signed_with(backend, ct, table)
+    }
+
+    /// Decrypt a ciphertext of a small integer `v` to get both the decrypted point vG,
+    /// which can be checked independently, and `v`, with the precomputed table `table`.
+    pub fn decrypt_value(
+        &self,
+        ct: Ciphertext<G1>,
+        table: &BsgsTable<G1>,
+    ) -> Result<(G1Affine, u64), Error> {
+        self.inner.decrypt_value(ct, table)
+    }
+
+    /// Decrypt a ciphertext `ct` of a small integer to get vG and `v` like
+    /// [`SecretKey::decrypt_value`], computing the scalar multiplication with the backend
+    /// `backend`.
+    pub fn decrypt_value_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G1>,
+        table: &BsgsTable<G1>,
+    ) -> Result<(G1Affine, u64), Error> {
+        self.inner.decrypt_value_with(backend, ct, table)
+    }
+
+    /// Rerandomize a ciphertext `ct` with randomness `r` under the public key of this
+    /// key, the same as [`PublicKey::rerandomize`] on [`SecretKey::public_key`], e.g. for
+    /// a decryptor in a mix step.
+    pub fn rerandomize(&self, ct: Ciphertext<G1>, r: Fr) -> Ciphertext<G1> {
+        self.inner.encrypt_key.rerandomize(ct, r)
+    }
+
+    /// Create a re-encryption key from this key to the key `to`. Both keys must have the
+    /// same generator, otherwise [`Error::GeneratorMismatch`] is returned.
+    pub fn rekey(&self, to: &SecretKey) -> Result<ReKey<G1>, Error> {
+        self.inner.rekey(&to.inner)
+    }
+
+    /// Get the public key in G2 with the G2 generator `h`, i.e. xH. It is published
+    /// alongside the public key for the pairing checks in [`pairing`].
+    pub fn public_key_g2(&self, h: G2Affine) -> G2Affine {
+        count_ops!(SCALAR_MULS);
+        count_ops!(NORMALIZATIONS);
+        (h * self.inner.secret).into_affine()
+    }
+
+    /// Get the secret x as its 32 canonical little-endian bytes, e.g. for an external key
+    /// store. The bytes are always fully reduced modulo the order of [`Fr`], and are the
+    /// same as the compressed arkworks serialization of x. The generator is not included,
+    /// see [`SecretKey::to_bytes_compact`] for a key with a custom generator.
+    pub fn secret_bytes(&self) -> [u8; 32] {
+        self.inner
+            .secret_bytes()
+            .try_into()
+            .expect("a scalar of BLS12-381 is 32 bytes")
+    }
+
+    /// Create a secret key with the standard G1 generator from the bytes of
+    /// [`SecretKey::secret_bytes`]. Returns [`Error::InvalidEncoding`] if the bytes are
+    /// not reduced, i.e. not less than the order of [`Fr`].
+    pub fn from_secret_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        Ok(Self {
+            inner: DecryptKey::from_secret_bytes(bytes)?,
+        })
+    }
+
+    /// Serialize the key without the public key, i.e. as the compressed generator (48
+    /// bytes) followed by the secret (32 bytes). This is about half the size of the serde
+    /// encoding, which is unchanged.
+    pub fn to_bytes_compact(&self) -> Vec<u8> {
+        self.inner.to_bytes_compact()
+    }
+
+    /// Deserialize a key serialized by [`SecretKey::to_bytes_compact`], deriving the public
+    /// key again from the generator and the secret.
+    pub fn from_bytes_compact(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            inner: DecryptKey::from_bytes_compact(bytes)?,
+        })
+    }
+
+    /// Decrypt the ciphertext `ct` and prove that the result is its message, without
+    /// pairings. The proof is verified by [`PublicKey::verify_decrypt_sigma`] against
+    /// the message returned by [`SecretKey::decrypt`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    /// let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    ///
+    /// let m = sk.decrypt(ct);
+    /// let proof = sk.prove_decrypt_sigma(&ct, rng);
+    /// assert!(pk.verify_decrypt_sigma(&ct, m, &proof));
+    /// ```
+    pub fn prove_decrypt_sigma<R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G1>,
+        rng: &mut R,
+    ) -> SigmaProof<G1> {
+        self.inner.prove_decrypt_sigma::<Sha256, _>(ct, rng)
+    }
+
+    /// Decrypt the ciphertexts `cts` and prove that the results are their messages with
+    /// a single proof of constant size, e.g. for a tallier publishing many decryptions.
+    /// The proof is verified by [`PublicKey::verify_decryption_batch`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    /// let cts = (0..10)
+    ///     .map(|_| pk.encrypt(G1Affine::rand(rng), Fr::rand(rng)))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let ms = sk.decrypt_batch(&cts);
+    /// let proof = sk.prove_decryption_batch(&cts, rng);
+    /// assert!(pk.verify_decryption_batch(&cts, &ms, &proof));
+    /// ```
+    pub fn prove_decryption_batch<R: CryptoRng + RngCore>(
+        &self,
+        cts: &[Ciphertext<G1>],
+        rng: &mut R,
+    ) -> BatchDecryptionProof<G1> {
+        self.inner.prove_decryption_batch::<Sha256, _>(cts, rng)
+    }
+
+    /// Prove that the public key of this key and the public key with the same secret
+    /// under the generator `g2` (i.e. of `SecretKey::new(g2, x)`) share the secret x,
+    /// without revealing it, e.g. to link an identity across two generators. The proof
+    /// is verified by [`PublicKey::verify_same_secret`].
+    pub fn prove_same_secret<R: CryptoRng + RngCore>(
+        &self,
+        g2: G1Affine,
+        rng: &mut R,
+    ) -> SameSecretProof<G1> {
+        self.inner.prove_same_secret::<Sha256, _>(g2, rng)
+    }
+
+    /// Decrypt the ciphertext `ct` and create a prover of the interactive protocol behind
+    /// [`SecretKey::prove_decrypt_sigma`], for use with external challenges. The
+    /// transcript is verified against [`PublicKey::decrypt_statement`].
+    pub fn decrypt_prover(&self, ct: &Ciphertext<G1>) -> SigmaProver<G1> {
+        self.inner.decrypt_prover(ct)
+    }
+
+    /// Combine the keys into the aggregate secret key x1 + ... + xn, which decrypts
+    /// ciphertexts under [`PublicKey::combine`] of their public keys. This requires all
+    /// parties to reveal their secrets to whoever combines them.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator.
+    pub fn combine_secret(keys: &[SecretKey]) -> Result<SecretKey, Error> {
+        let keys = keys.iter().map(|k| k.inner).collect::<Vec<_>>();
+        Ok(SecretKey {
+            inner: DecryptKey::combine_secret(&keys)?,
+        })
+    }
+
+    /// Check that the stored public key matches the secret, see
+    /// [`DecryptKey::validate`]. Keys loaded with an embedded public key are checked on
+    /// load, so this is for keys kept in memory or storage for a long time.
+    pub fn validate(&self) -> bool {
+        self.inner.validate()
+    }
+
     /// Get the public key from the secret key.
     pub fn public_key(&self) -> PublicKey {
         PublicKey {
@@ -71,6 +603,7 @@ impl SecretKey {
 ///
 /// The public key is created from the secret key [`SecretKey`].
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PublicKey {
     inner: EncryptKey<G1>,
 }
@@ -100,6 +633,385 @@ impl PublicKey {
         self.inner.encrypt(m, r)
     }
 
+    /// Encrypt a message `m` with randomness `r` like [`PublicKey::encrypt`], or return
+    /// [`Error::DegenerateKey`] if the key is the identity (see
+    /// [`PublicKey::is_degenerate`]), e.g. for keys received from untrusted parties.
+    pub fn try_encrypt(&self, m: G1Affine, r: Fr) -> Result<Ciphertext<G1>, Error> {
+        self.inner.try_encrypt(m, r)
+    }
+
+    /// Encrypt the messages `messages` all with the same randomness `r`, computing rG and
+    /// rY once. The ciphertexts reveal the differences of their messages, see
+    /// [`EncryptKey::encrypt_reusing_ephemeral`] before using it.
+    pub fn encrypt_reusing_ephemeral(&self, messages: &[G1Affine], r: Fr) -> Vec<Ciphertext<G1>> {
+        self.inner.encrypt_reusing_ephemeral(messages, r)
+    }
+
+    /// Check if the key is the identity, i.e. created from the secret zero, in which case
+    /// [`PublicKey::encrypt`] leaves messages in the clear.
+    pub fn is_degenerate(&self) -> bool {
+        self.inner.is_degenerate()
+    }
+
+    /// Encrypt a message `m` with randomness `r` like [`PublicKey::encrypt`], computing
+    /// the scalar multiplications with the backend `backend`, e.g. a hardware accelerator.
+    pub fn encrypt_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        m: G1Affine,
+        r: Fr,
+    ) -> Ciphertext<G1> {
+        self.inner.encrypt_with(backend, m, r)
+    }
+
+    /// Encrypt a message `m` with randomness given as [`util::RANDOM_BYTES_SIZE`] (64)
+    /// raw bytes, e.g. from a hardware RNG, to get the ciphertext and the randomness r.
+    /// The bytes are read as a little-endian integer and reduced modulo the order of
+    /// [`Fr`] in constant time (see [`util::reduce_bytes_ct`]), so the same bytes always
+    /// give the same ciphertext.
+    ///
+    /// Returns [`Error::InvalidRandomness`] if `rand_bytes` is not 64 bytes long.
+    pub fn encrypt_from_randomness_bytes(
+        &self,
+        m: G1Affine,
+        rand_bytes: &[u8],
+    ) -> Result<(Ciphertext<G1>, Fr), Error> {
+        self.inner.encrypt_from_randomness_bytes(m, rand_bytes)
+    }
+
+    /// Encrypt a message `m` with randomness `r` bound to the context `label`, i.e. as the
+    /// message m + H(label), where H is the hash to curve [`generators::label_point`].
+    /// Only [`SecretKey::decrypt_with_label`] with the same label recovers `m`, so a
+    /// ciphertext accidentally decrypted in another context gives an unrelated point.
+    ///
+    /// Labels are public and the encryption is additively homomorphic, so this does not
+    /// prevent reuse across contexts: anyone can move a ciphertext from label `a` to
+    /// label `b` by adding H(b) - H(a) to its second component. Bind the ciphertext to
+    /// its context with a proof or signature where that matters.
+    pub fn encrypt_with_label(&self, m: G1Affine, label: &[u8], r: Fr) -> Ciphertext<G1> {
+        self.inner
+            .encrypt_projective(m + generators::label_point(label), r)
+    }
+
+    /// Encrypt a message `m` given as a projective point with randomness `r` to get a
+    /// ciphertext. It saves the inversion of converting `m` to affine, e.g. when `m` is
+    /// the result of other group operations.
+    pub fn encrypt_projective(&self, m: G1, r: Fr) -> Ciphertext<G1> {
+        self.inner.encrypt_projective(m, r)
+    }
+
+    /// Encrypt a projective message `m` with randomness `r` like
+    /// [`PublicKey::encrypt_projective`], computing the scalar multiplications with the
+    /// backend `backend`.
+    pub fn encrypt_projective_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        m: G1,
+        r: Fr,
+    ) -> Ciphertext<G1> {
+        self.inner.encrypt_projective_with(backend, m, r)
+    }
+
+    /// Encrypt a random message with fresh randomness, e.g. for a mix-net or PIR to pad
+    /// a batch to a fixed count. Unlike an encryption of the identity, which an adversary
+    /// can guess and test for, a dummy is indistinguishable from real ciphertexts. It
+    /// decrypts to a random point, so callers must filter dummies after decryption.
+    pub fn dummy_ciphertext<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Ciphertext<G1> {
+        self.inner.dummy_ciphertext(rng)
+    }
+
+    /// Encrypt a small integer `v` with randomness `r` to get a ciphertext. The integer
+    /// can be recovered by [`SecretKey::decrypt_scalar`].
+    pub fn encrypt_scalar(&self, v: u64, r: Fr) -> Ciphertext<G1> {
+        self.inner.encrypt_scalar(v, r)
+    }
+
+    /// Encrypt a small integer `v` with randomness `r` like [`PublicKey::encrypt_scalar`],
+    /// or return [`Error::OutOfRange`] if `v` is larger than `max`. `max` should match
+    /// [`BsgsTable::max`] of the table used by [`SecretKey::decrypt_scalar`], so that an
+    /// undecryptable ciphertext is never produced.
+    pub fn encrypt_scalar_bounded(&self, v: u64, max: u64, r: Fr) -> Result<Ciphertext<G1>, Error> {
+        self.inner.encrypt_scalar_bounded(v, max, r)
+    }
+
+    /// Encrypt a small signed integer `v` with randomness `r` to get a ciphertext, e.g. a
+    /// balance that can go negative. Negative values are encoded as their field negation,
+    /// so sums of positive and negative amounts decrypt to the signed total with
+    /// [`SecretKey::decrypt_signed`].
+    pub fn encrypt_signed(&self, v: i64, r: Fr) -> Ciphertext<G1> {
+        self.inner.encrypt_signed(v, r)
+    }
+
+    /// Encrypt the bytes `msg` as the chunks of [`encoding::encode_chunks`], each with
+    /// fresh randomness, decrypted by [`SecretKey::decrypt_chunks`]. Every 3 bytes take a
+    /// ciphertext, so `PublicKey::seal_to_many` (with the `seal` feature) suits long data
+    /// better, unless the chunks must stay homomorphic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `msg` is longer than [`encoding::CHUNK_MAX`] bytes.
+    pub fn encrypt_chunks<R: CryptoRng + RngCore>(
+        &self,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Vec<Ciphertext<G1>> {
+        encoding::encode_chunks(msg)
+            .into_iter()
+            .map(|m| self.encrypt(m, Fr::rand(rng)))
+            .collect()
+    }
+
+    /// Re-encrypt a ciphertext `ct` to this key with the re-encryption key `rk`, and
+    /// rerandomize it with randomness `r`, in one pass.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let g1 = G1Affine::rand(rng);
+    ///
+    /// let sk1 = SecretKey::new(g1, Fr::rand(rng));
+    /// let sk2 = SecretKey::new(g1, Fr::rand(rng));
+    /// let rk = sk1.rekey(&sk2).unwrap();
+    ///
+    /// let m = G1Affine::rand(rng);
+    /// let ct = sk1.public_key().encrypt(m, Fr::rand(rng));
+    /// let new_ct = sk2.public_key().rerandomize_and_rekey(ct, &rk, Fr::rand(rng));
+    ///
+    /// assert_eq!(sk2.decrypt(new_ct), m);
+    /// ```
+    pub fn rerandomize_and_rekey(
+        &self,
+        ct: Ciphertext<G1>,
+        rk: &ReKey<G1>,
+        r: Fr,
+    ) -> Ciphertext<G1> {
+        self.inner.rerandomize_and_rekey(ct, rk, r)
+    }
+
+    /// Re-encrypt and rerandomize a ciphertext `ct` like
+    /// [`PublicKey::rerandomize_and_rekey`], computing the scalar multiplications with the
+    /// backend `backend`.
+    pub fn rerandomize_and_rekey_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G1>,
+        rk: &ReKey<G1>,
+        r: Fr,
+    ) -> Ciphertext<G1> {
+        self.inner.rerandomize_and_rekey_with(backend, ct, rk, r)
+    }
+
+    /// Verify that the ciphertext `ct` decrypts to the message `m` under this key, with
+    /// a proof from [`SecretKey::prove_decrypt_sigma`].
+    pub fn verify_decrypt_sigma(
+        &self,
+        ct: &Ciphertext<G1>,
+        m: G1Affine,
+        proof: &SigmaProof<G1>,
+    ) -> bool {
+        self.inner.verify_decrypt_sigma::<Sha256>(ct, m, proof)
+    }
+
+    /// Encrypt the identity with fresh randomness r and prove that it is an encryption of
+    /// the identity, e.g. to publish a rerandomization token for a mix-net that provably
+    /// does not change the message of the ciphertext it is added to. Returns the
+    /// ciphertext, r and the proof, which is verified by [`PublicKey::verify_zero`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    /// let m = G1Affine::rand(rng);
+    /// let ct = pk.encrypt(m, Fr::rand(rng));
+    ///
+    /// let (token, _, proof) = pk.encrypt_zero_proved(rng);
+    /// assert!(pk.verify_zero(&token, &proof));
+    /// assert_eq!(sk.decrypt(ct + token), m);
+    /// ```
+    pub fn encrypt_zero_proved<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> (Ciphertext<G1>, Fr, ZeroProof<G1>) {
+        self.inner.encrypt_zero_proved::<Sha256, _>(rng)
+    }
+
+    /// Verify that the ciphertext `ct` encrypts the identity under this key, with a proof
+    /// from [`PublicKey::encrypt_zero_proved`].
+    pub fn verify_zero(&self, ct: &Ciphertext<G1>, proof: &ZeroProof<G1>) -> bool {
+        self.inner.verify_zero::<Sha256>(ct, proof)
+    }
+
+    /// Verify that the ciphertexts `cts` decrypt to the messages `ms` under this key, in
+    /// order, with a proof from [`SecretKey::prove_decryption_batch`].
+    pub fn verify_decryption_batch(
+        &self,
+        cts: &[Ciphertext<G1>],
+        ms: &[G1Affine],
+        proof: &BatchDecryptionProof<G1>,
+    ) -> bool {
+        self.inner.verify_decryption_batch::<Sha256>(cts, ms, proof)
+    }
+
+    /// Get the statement that the ciphertext `ct` decrypts to the message `m` under this
+    /// key, which verifies the transcripts of [`SecretKey::decrypt_prover`].
+    pub fn decrypt_statement(&self, ct: &Ciphertext<G1>, m: G1Affine) -> DleqStatement<G1> {
+        self.inner.decrypt_statement(ct, m)
+    }
+
+    /// Prove that the ciphertext `ct`, created with randomness `r_enc` as an encryption of
+    /// vP (e.g. by [`PublicKey::encrypt_scalar`]), encrypts the same scalar `v` as the
+    /// Pedersen commitment `commitment` = vP + sH with the blinding `r_com`, under
+    /// [`PedersenParams::standard`]. E.g. in escrow, a value is committed publicly and
+    /// encrypted for an arbiter, who can then trust the ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, G1Affine, PedersenParams, SecretKey};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    ///
+    /// let (r_enc, r_com) = (Fr::rand(rng), Fr::rand(rng));
+    /// let commitment = PedersenParams::standard().commit(Fr::from(42), r_com);
+    /// let ct = pk.encrypt_scalar(42, r_enc);
+    ///
+    /// let proof = pk.prove_encryption_matches_commitment(commitment, &ct, Fr::from(42), r_enc, r_com, rng);
+    /// assert!(pk.verify_encryption_matches_commitment(commitment, &ct, &proof));
+    /// ```
+    pub fn prove_encryption_matches_commitment<R: CryptoRng + RngCore>(
+        &self,
+        commitment: G1Affine,
+        ct: &Ciphertext<G1>,
+        v: Fr,
+        r_enc: Fr,
+        r_com: Fr,
+        rng: &mut R,
+    ) -> MatchProof<G1> {
+        self.inner.prove_encryption_matches_commitment::<Sha256, _>(
+            &PedersenParams::standard(),
+            commitment,
+            ct,
+            v,
+            r_enc,
+            r_com,
+            rng,
+        )
+    }
+
+    /// Verify that the ciphertext `ct` encrypts the scalar committed to in `commitment`
+    /// under [`PedersenParams::standard`], with a proof from
+    /// [`PublicKey::prove_encryption_matches_commitment`].
+    pub fn verify_encryption_matches_commitment(
+        &self,
+        commitment: G1Affine,
+        ct: &Ciphertext<G1>,
+        proof: &MatchProof<G1>,
+    ) -> bool {
+        self.inner.verify_encryption_matches_commitment::<Sha256>(
+            &PedersenParams::standard(),
+            commitment,
+            ct,
+            proof,
+        )
+    }
+
+    /// Verify that this key and `other`, under their respective generators, share the
+    /// same secret, with a proof from [`SecretKey::prove_same_secret`].
+    pub fn verify_same_secret(&self, other: &PublicKey, proof: &SameSecretProof<G1>) -> bool {
+        self.inner.verify_same_secret::<Sha256>(&other.inner, proof)
+    }
+
+    /// Combine the public keys into an aggregate key Y = Y1 + ... + Yn with the shared
+    /// generator. A message encrypted under the aggregate key can only be decrypted with
+    /// the sum of all secrets x1 + ... + xn (see [`SecretKey::combine_secret`]), e.g. for a
+    /// simple n-of-n joint decryption. The aggregate does not depend on the order of the
+    /// keys, since point addition commutes, so no canonical ordering is needed; sort the
+    /// keys (see the `Ord` impl) only when hashing the set itself.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator.
+    pub fn combine(keys: &[PublicKey]) -> Result<PublicKey, Error> {
+        let keys = keys.iter().map(|k| k.inner).collect::<Vec<_>>();
+        Ok(PublicKey {
+            inner: EncryptKey::combine(&keys)?,
+        })
+    }
+
+    /// Encrypt a message `m` with randomness `r` to all the public `keys` at once, e.g. for
+    /// a broadcast. The recipients share the component a = rG, and the ciphertext of
+    /// recipient i is `multi[i]`, decrypted with its secret key alone.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator.
+    pub fn encrypt_multi(
+        keys: &[PublicKey],
+        m: G1Affine,
+        r: Fr,
+    ) -> Result<MultiCiphertext<G1>, Error> {
+        let keys = keys.iter().map(|k| k.inner).collect::<Vec<_>>();
+        MultiCiphertext::encrypt(&keys, m, r)
+    }
+
+    /// Check whether this key and `other` share the same generator. Operations across
+    /// keys, such as [`PublicKey::combine`], [`PublicKey::encrypt_multi`] and
+    /// [`SecretKey::rekey`], require it and
+    /// return [`Error::GeneratorMismatch`] otherwise.
+    pub fn same_generator(&self, other: &PublicKey) -> bool {
+        self.inner.same_generator(&other.inner)
+    }
+
+    /// Create a public key from its compressed generator and Y (48 bytes each), checking
+    /// that both are valid points of G1. Decompressing and validating is the expensive
+    /// part, so when encrypting many messages to a stored key, create the key once and
+    /// reuse it.
+    ///
+    /// Returns [`Error::InvalidEncoding`] if either point is malformed, or
+    /// [`Error::InvalidGenerator`] if the generator is the identity.
+    pub fn from_compressed(generator_bytes: &[u8], y_bytes: &[u8]) -> Result<PublicKey, Error> {
+        Ok(PublicKey {
+            inner: EncryptKey::from_compressed(generator_bytes, y_bytes)?,
+        })
+    }
+
+    /// Encrypt a message `m` with randomness `r` to the public key given by its compressed
+    /// generator and Y, e.g. a key stored as bytes, without keeping a [`PublicKey`]. The
+    /// points are validated as in [`PublicKey::from_compressed`].
+    pub fn encrypt_with_compressed(
+        generator_bytes: &[u8],
+        y_bytes: &[u8],
+        m: G1Affine,
+        r: Fr,
+    ) -> Result<Ciphertext<G1>, Error> {
+        EncryptKey::encrypt_with_compressed(generator_bytes, y_bytes, m, r)
+    }
+
+    /// Hash the domain string `dst` to a nothing-up-my-sleeve point of G1, the same as
+    /// [`generators::nums`].
+    pub fn nums_generator(dst: &[u8]) -> G1Affine {
+        generators::nums(dst)
+    }
+
+    /// Get the group generator (G).
+    pub fn generator(&self) -> G1Affine {
+        self.inner.generator()
+    }
+
+    /// Get the component Y (= xG) where x is the secret key.
+    pub fn y(&self) -> G1Affine {
+        self.inner.y()
+    }
+
     /// Rerandomize a ciphertext `ct` with randomness `r`.
     ///
     /// # Example
@@ -129,4 +1041,193 @@ impl PublicKey {
     pub fn rerandomize(&self, ct: Ciphertext<G1>, r: Fr) -> Ciphertext<G1> {
         self.inner.rerandomize(ct, r)
     }
+
+    /// Rerandomize a ciphertext `ct` with randomness `r` like [`PublicKey::rerandomize`],
+    /// computing the scalar multiplications with the backend `backend`.
+    pub fn rerandomize_with<M: ScalarMul<G1>>(
+        &self,
+        backend: &M,
+        ct: Ciphertext<G1>,
+        r: Fr,
+    ) -> Ciphertext<G1> {
+        self.inner.rerandomize_with(backend, ct, r)
+    }
+
+    /// Rerandomize a ciphertext `ct` with randomness given as [`util::RANDOM_BYTES_SIZE`]
+    /// (64) raw bytes, e.g. from a hardware RNG of a mix-net node. The bytes are reduced
+    /// into [`Fr`] as in [`PublicKey::encrypt_from_randomness_bytes`], so the same bytes
+    /// always give the same ciphertext.
+    ///
+    /// Returns [`Error::InvalidRandomness`] if `rand_bytes` is not 64 bytes long.
+    pub fn rerandomize_from_bytes(
+        &self,
+        ct: Ciphertext<G1>,
+        rand_bytes: &[u8],
+    ) -> Result<Ciphertext<G1>, Error> {
+        self.inner.rerandomize_from_bytes(ct, rand_bytes)
+    }
+
+    /// Refresh a stored ciphertext without changing its message: rerandomize it with
+    /// fresh randomness from `rng` and normalize both points to their canonical form.
+    pub fn refresh<R: CryptoRng + RngCore>(
+        &self,
+        ct: Ciphertext<G1>,
+        rng: &mut R,
+    ) -> Ciphertext<G1> {
+        self.inner.refresh(ct, rng)
+    }
+
+    /// Encrypt a message `m` with a fresh one-time key pair sampled from `rng`, in the
+    /// style of ECIES. The result names the ephemeral public key and the payload, which
+    /// are the two points of the ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, G1Affine, SecretKey};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::from_scalar(Fr::rand(rng));
+    ///
+    /// let m = G1Affine::rand(rng);
+    /// let sealed = sk.public_key().encrypt_ephemeral(m, rng);
+    /// assert_eq!(sk.decrypt(sealed.into()), m);
+    /// ```
+    pub fn encrypt_ephemeral<R: CryptoRng + RngCore>(
+        &self,
+        m: G1Affine,
+        rng: &mut R,
+    ) -> EphemeralEncryption<G1> {
+        self.inner.encrypt_ephemeral(m, rng)
+    }
+
+    /// Prove that the ciphertext `ct`, created with randomness `r`, encrypts
+    /// `candidates[1]` if `actual` is true and `candidates[0]` otherwise, without
+    /// revealing which. E.g. a yes/no ballot proves it encrypts 0G or 1G.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_ec::AffineRepr;
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{Fr, SecretKey, G1Affine};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    ///
+    /// let candidates = [G1Affine::zero(), G1Affine::generator()];
+    /// let r = Fr::rand(rng);
+    /// let ct = pk.encrypt(candidates[1], r);
+    ///
+    /// let proof = pk.prove_encryption_of_one_of(&ct, r, true, candidates, rng);
+    /// assert!(pk.verify_encryption_of_one_of(&ct, candidates, &proof));
+    /// ```
+    pub fn prove_encryption_of_one_of<R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G1>,
+        r: Fr,
+        actual: bool,
+        candidates: [G1Affine; 2],
+        rng: &mut R,
+    ) -> OrProof<G1> {
+        self.inner
+            .prove_encryption_of_one_of::<Sha256, _>(ct, r, actual, candidates, rng)
+    }
+
+    /// Encrypt the bit `b` with fresh randomness, with a proof that the plaintext is 0
+    /// or 1. Bits add up to an encrypted count, which decrypts with
+    /// [`SecretKey::decrypt_scalar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ark_std::UniformRand;
+    /// use bls_elgamal::{BsgsTable, Fr, G1Affine, SecretKey};
+    ///
+    /// let rng = &mut rand::thread_rng();
+    /// let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    /// let pk = sk.public_key();
+    ///
+    /// let (yes, no) = (pk.encrypt_bit(true, rng), pk.encrypt_bit(false, rng));
+    /// assert!(yes.verify(&pk) && no.verify(&pk));
+    ///
+    /// let count = yes + no;
+    /// assert_eq!(sk.decrypt_scalar(count, &BsgsTable::new(2, 1 << 10).unwrap()), Ok(1));
+    /// ```
+    pub fn encrypt_bit<R: CryptoRng + RngCore>(&self, b: bool, rng: &mut R) -> EncryptedBit<G1> {
+        self.inner.encrypt_bit::<Sha256, _>(b, rng)
+    }
+
+    /// Verify that the ciphertext `ct` encrypts one of the two `candidates`.
+    pub fn verify_encryption_of_one_of(
+        &self,
+        ct: &Ciphertext<G1>,
+        candidates: [G1Affine; 2],
+        proof: &OrProof<G1>,
+    ) -> bool {
+        self.inner
+            .verify_encryption_of_one_of::<Sha256>(ct, candidates, proof)
+    }
+
+    /// Prove that the ciphertext `ct`, created with randomness `r`, encrypts
+    /// `candidates[actual_index]`, without revealing the index, e.g. for a
+    /// multiple-choice ballot. The proof holds two scalars per candidate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `actual_index` is not an index of `candidates`.
+    pub fn prove_encryption_of_one_of_many<R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G1>,
+        r: Fr,
+        actual_index: usize,
+        candidates: &[G1Affine],
+        rng: &mut R,
+    ) -> OneOfManyProof<G1> {
+        self.inner.prove_encryption_of_one_of_many::<Sha256, _>(
+            ct,
+            r,
+            actual_index,
+            candidates,
+            rng,
+        )
+    }
+
+    /// Verify that the ciphertext `ct` encrypts one of the `candidates`.
+    pub fn verify_encryption_of_one_of_many(
+        &self,
+        ct: &Ciphertext<G1>,
+        candidates: &[G1Affine],
+        proof: &OneOfManyProof<G1>,
+    ) -> bool {
+        self.inner
+            .verify_encryption_of_one_of_many::<Sha256>(ct, candidates, proof)
+    }
+}
+
+// Serialize the wrappers in the same shape as their derived impls, with the inner key
+// uncompressed
+
+impl Serialize for Uncompressed<SecretKey> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("SecretKey", 1)?;
+        state.serialize_field("inner", &Uncompressed(self.0.inner))?;
+        state.end()
+    }
+}
+
+impl Serialize for Uncompressed<PublicKey> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("PublicKey", 1)?;
+        state.serialize_field("inner", &Uncompressed(self.0.inner))?;
+        state.end()
+    }
 }
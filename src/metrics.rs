@@ -0,0 +1,47 @@
+//! Counters of the cryptographic operations performed by the crate, for profiling
+//! without external tools, e.g. to see how many normalizations a batch operation saves.
+//!
+//! The counters are global and updated with relaxed atomics, so they are only exact when
+//! no other thread uses the crate at the same time. They cover encryption, decryption,
+//! rerandomization, re-encryption, the homomorphic operations on ciphertexts, the
+//! discrete log search and the pairing checks; the zero-knowledge proofs are not counted.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub(crate) static SCALAR_MULS: AtomicU64 = AtomicU64::new(0);
+pub(crate) static PAIRINGS: AtomicU64 = AtomicU64::new(0);
+pub(crate) static NORMALIZATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The numbers of operations counted since the start of the program or the last
+/// [`reset`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OpCounts {
+    /// Scalar multiplications of a point. A multi-scalar multiplication counts one per
+    /// term.
+    pub scalar_muls: u64,
+    /// Pairings. A multi-pairing counts one per pair.
+    pub pairings: u64,
+    /// Conversions to affine form. A batch normalization counts once, as it shares one
+    /// field inversion among all of its points.
+    pub normalizations: u64,
+}
+
+/// Read the current counts.
+pub fn snapshot() -> OpCounts {
+    OpCounts {
+        scalar_muls: SCALAR_MULS.load(Ordering::Relaxed),
+        pairings: PAIRINGS.load(Ordering::Relaxed),
+        normalizations: NORMALIZATIONS.load(Ordering::Relaxed),
+    }
+}
+
+/// Reset all counts to zero.
+pub fn reset() {
+    SCALAR_MULS.store(0, Ordering::Relaxed);
+    PAIRINGS.store(0, Ordering::Relaxed);
+    NORMALIZATIONS.store(0, Ordering::Relaxed);
+}
+
+pub(crate) fn record(counter: &AtomicU64, n: u64) {
+    counter.fetch_add(n, Ordering::Relaxed);
+}
@@ -0,0 +1,110 @@
+use std::ops::Index;
+
+use ark_ec::{CurveGroup, PrimeGroup};
+
+use super::{ciphertext::Ciphertext, encrypt::EncryptKey, error::Error};
+
+/// A message encrypted to several recipients with the same randomness r, i.e. the shared
+/// component a = rG and one component b_i = m + rY_i per recipient.
+///
+/// The ciphertext of recipient i is (a, b_i), available by index, [`MultiCiphertext::get`]
+/// or iteration, and decrypts with the key of that recipient alone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiCiphertext<G: CurveGroup> {
+    cts: Vec<Ciphertext<G>>,
+}
+
+impl<G: CurveGroup> MultiCiphertext<G> {
+    /// Encrypt a message `m` with randomness `r` to every key of `keys`, in order.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no keys, or [`Error::GeneratorMismatch`]
+    /// if they do not share the same generator (the shared component a requires it).
+    pub fn encrypt(
+        keys: &[EncryptKey<G>],
+        m: G::Affine,
+        r: <G as PrimeGroup>::ScalarField,
+    ) -> Result<Self, Error> {
+        let (first, rest) = keys.split_first().ok_or(Error::EmptyInput)?;
+        if rest.iter().any(|k| !k.same_generator(first)) {
+            return Err(Error::GeneratorMismatch);
+        }
+        count_ops!(SCALAR_MULS, 1 + keys.len());
+        let a = first.generator * r;
+        let cts = keys.iter().map(|k| Ciphertext(a, k.y * r + m)).collect();
+        Ok(Self { cts })
+    }
+
+    /// Create a multi-recipient ciphertext from the shared component `a` and the
+    /// components `bs` of the recipients, in order, e.g. after deserializing them.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no recipients.
+    pub fn from_parts(a: G, bs: Vec<G>) -> Result<Self, Error> {
+        if bs.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+        let cts = bs.into_iter().map(|b| Ciphertext(a, b)).collect();
+        Ok(Self { cts })
+    }
+
+    /// Get the component a = rG shared by all recipients.
+    pub fn shared_a(&self) -> G {
+        self.cts[0].0
+    }
+
+    /// Get the ciphertext of recipient `i`, or `None` if there is no such recipient.
+    pub fn get(&self, i: usize) -> Option<&Ciphertext<G>> {
+        self.cts.get(i)
+    }
+
+    /// Get the number of recipients.
+    pub fn len(&self) -> usize {
+        self.cts.len()
+    }
+
+    /// Check if there are no recipients, which is never the case for a ciphertext
+    /// created by [`MultiCiphertext::encrypt`].
+    pub fn is_empty(&self) -> bool {
+        self.cts.is_empty()
+    }
+
+    /// Iterate over the ciphertexts of the recipients, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Ciphertext<G>> {
+        self.cts.iter()
+    }
+}
+
+impl<G: CurveGroup> Index<usize> for MultiCiphertext<G> {
+    type Output = Ciphertext<G>;
+
+    /// Get the ciphertext of recipient `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the number of recipients.
+    fn index(&self, i: usize) -> &Ciphertext<G> {
+        self.get(i).unwrap_or_else(|| {
+            panic!(
+                "recipient index {i} is out of {} recipients",
+                self.cts.len()
+            )
+        })
+    }
+}
+
+impl<G: CurveGroup> IntoIterator for MultiCiphertext<G> {
+    type Item = Ciphertext<G>;
+    type IntoIter = std::vec::IntoIter<Ciphertext<G>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cts.into_iter()
+    }
+}
+
+impl<'a, G: CurveGroup> IntoIterator for &'a MultiCiphertext<G> {
+    type Item = &'a Ciphertext<G>;
+    type IntoIter = std::slice::Iter<'a, Ciphertext<G>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cts.iter()
+    }
+}
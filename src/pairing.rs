@@ -0,0 +1,58 @@
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
+use ark_std::Zero;
+
+use super::{Ciphertext, G1Affine, G2Affine, PublicKey, G1};
+
+/// Check that the ciphertext `ct` = (a, b) encrypts the message `m` without the secret
+/// key, by the pairing equation e(a, Y2) = e(b - m, H).
+///
+/// BLS12-381 is an asymmetric pairing, so the check needs the public key in G2:
+/// `h` is a generator of G2 and `y2` = xH is obtained from
+/// [`SecretKey::public_key_g2`](crate::SecretKey::public_key_g2). The key pair
+/// (`h`, `y2`) should be checked against the G1 public key once with
+/// [`verify_public_key_g2`].
+///
+/// Unlike a proof of correct decryption, the check requires revealing `m`, which makes
+/// it suitable for public audit logs of decrypted values.
+pub fn verify_ciphertext(h: G2Affine, y2: G2Affine, ct: Ciphertext<G1>, m: G1Affine) -> bool {
+    // e(a, Y2) = e(b - m, H) <=> e(a, Y2) + e(m - b, H) = 0 (in additive notation)
+    count_ops!(PAIRINGS, 2);
+    Bls12_381::multi_pairing([ct.0, m - ct.1], [y2, h]).is_zero()
+}
+
+/// Check that the G2 key pair (`h`, `y2`) shares the secret of the public key `pk`,
+/// i.e. Y = xG and Y2 = xH for the same x, by the pairing equation e(Y, H) = e(G, Y2).
+pub fn verify_public_key_g2(pk: &PublicKey, h: G2Affine, y2: G2Affine) -> bool {
+    count_ops!(PAIRINGS, 2);
+    Bls12_381::multi_pairing([pk.y(), -pk.generator()], [h, y2]).is_zero()
+}
+
+/// A verifier for [`verify_ciphertext`] against a fixed G2 key pair (`h`, `y2`).
+///
+/// The line functions of the Miller loop for `h` and `y2` are precomputed once
+/// ([`G2Prepared`](ark_ec::pairing::Pairing::G2Prepared)), so each verification only
+/// runs the multi Miller loop and the final exponentiation.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifier {
+    h: <Bls12_381 as Pairing>::G2Prepared,
+    y2: <Bls12_381 as Pairing>::G2Prepared,
+}
+
+impl PreparedVerifier {
+    /// Precompute the verifier for the G2 generator `h` and the G2 public key `y2`.
+    pub fn new(h: G2Affine, y2: G2Affine) -> Self {
+        Self {
+            h: h.into(),
+            y2: y2.into(),
+        }
+    }
+
+    /// Check that the ciphertext `ct` encrypts the message `m`, with the same pairing
+    /// equation as [`verify_ciphertext`].
+    pub fn verify(&self, ct: Ciphertext<G1>, m: G1Affine) -> bool {
+        count_ops!(PAIRINGS, 2);
+        let mlo = Bls12_381::multi_miller_loop([ct.0, m - ct.1], [self.y2.clone(), self.h.clone()]);
+        Bls12_381::final_exponentiation(mlo).is_some_and(|out| out.is_zero())
+    }
+}
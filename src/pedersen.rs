@@ -0,0 +1,66 @@
+//! Pedersen commitments C = vG + sH to scalars v with blinding s, which are perfectly
+//! hiding and binding as long as the discrete log of H with respect to G is unknown.
+
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+
+use super::generators;
+
+/// The domain string of the base H of [`PedersenParams::standard`].
+const PEDERSEN_H_DST: &[u8] = b"bls-elgamal/pedersen-h";
+
+/// The bases G and H of Pedersen commitments.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PedersenParams<G: CurveGroup> {
+    pub(crate) g: G,
+    pub(crate) h: G,
+}
+
+impl<G: CurveGroup> PedersenParams<G> {
+    /// Create the parameters with bases `g` and `h`. Nobody may know the discrete log of
+    /// `h` with respect to `g`, otherwise commitments are not binding; see
+    /// [`generators::nums`] for such a base.
+    pub fn new(g: G::Affine, h: G::Affine) -> Self {
+        Self {
+            g: g.into_group(),
+            h: h.into_group(),
+        }
+    }
+
+    /// Commit to the scalar `v` with the blinding `blinding`, i.e. vG + sH.
+    pub fn commit(
+        &self,
+        v: <G as PrimeGroup>::ScalarField,
+        blinding: <G as PrimeGroup>::ScalarField,
+    ) -> G::Affine {
+        (self.g * v + self.h * blinding).into_affine()
+    }
+
+    /// Check that `commitment` opens to `v` with the blinding `blinding`.
+    pub fn open(
+        &self,
+        commitment: G::Affine,
+        v: <G as PrimeGroup>::ScalarField,
+        blinding: <G as PrimeGroup>::ScalarField,
+    ) -> bool {
+        self.commit(v, blinding) == commitment
+    }
+
+    /// Get the base G of the committed value.
+    pub fn g(&self) -> G::Affine {
+        self.g.into_affine()
+    }
+
+    /// Get the base H of the blinding.
+    pub fn h(&self) -> G::Affine {
+        self.h.into_affine()
+    }
+}
+
+impl PedersenParams<ark_bls12_381::G1Projective> {
+    /// The standard parameters over G1: G is the standard generator, the same base as
+    /// the messages of [`PublicKey::encrypt_scalar`](crate::PublicKey::encrypt_scalar),
+    /// and H is the nothing-up-my-sleeve point `generators::nums(b"bls-elgamal/pedersen-h")`.
+    pub fn standard() -> Self {
+        Self::new(generators::G1_STANDARD, generators::nums(PEDERSEN_H_DST))
+    }
+}
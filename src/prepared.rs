@@ -0,0 +1,85 @@
+//! Decryption with precomputation on the secret key, for decryptors that receive
+//! ciphertexts late and want the shortest latency once they arrive.
+
+use ark_bls12_381::{g1::Config, Fr, G1Affine, G1Projective};
+use ark_ec::{scalar_mul::glv::GLVConfig, AdditiveGroup, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+
+use super::{ciphertext::Ciphertext, decrypt::DecryptKey};
+
+/// The width of the non-adjacent forms of the halves of the secret.
+const WINDOW: usize = 4;
+
+/// A decryption key with the scalar multiplication by its secret prepared, created by
+/// [`DecryptKey::prepare`].
+///
+/// Decryption computes b - xa, where only x is known in advance. The negated secret -x
+/// is split once into two halves of about 128 bits with the GLV endomorphism of G1,
+/// -x = k1 + λk2, and each half is recoded into its width-4 non-adjacent form. Then
+/// the online multiplication of a ciphertext only builds two tables of four odd
+/// multiples and runs a single loop of about 128 doublings, where the generic scalar
+/// multiplication decomposes the scalar again and doubles for every bit of the field.
+#[derive(Clone, Debug)]
+pub struct PreparedDecryptKey {
+    /// The signs of k1 and k2, true if positive.
+    signs: (bool, bool),
+    /// The digits of |k1| and |k2|, least significant first, padded to the same length.
+    digits: Vec<(i64, i64)>,
+}
+
+impl DecryptKey<G1Projective> {
+    /// Precompute the multiplication by the secret, see [`PreparedDecryptKey`].
+    pub fn prepare(&self) -> PreparedDecryptKey {
+        let ((sign1, k1), (sign2, k2)) = Config::scalar_decomposition(-self.secret);
+        let wnaf = |k: Fr| {
+            k.into_bigint()
+                .find_wnaf(WINDOW)
+                .expect("the window is supported")
+        };
+        let (d1, d2) = (wnaf(k1), wnaf(k2));
+        let len = d1.len().max(d2.len());
+        let digit = |d: &[i64], i: usize| d.get(i).copied().unwrap_or(0);
+        PreparedDecryptKey {
+            signs: (sign1, sign2),
+            digits: (0..len).map(|i| (digit(&d1, i), digit(&d2, i))).collect(),
+        }
+    }
+}
+
+impl PreparedDecryptKey {
+    /// Decrypt a ciphertext (a, b) to get b - ax, the same as [`DecryptKey::decrypt`].
+    pub fn decrypt(&self, ct: Ciphertext<G1Projective>) -> G1Affine {
+        count_ops!(SCALAR_MULS);
+        count_ops!(NORMALIZATIONS);
+        let (b1, b2) = (ct.0, Config::endomorphism(&ct.0));
+        let table1 = odd_multiples(if self.signs.0 { b1 } else { -b1 });
+        let table2 = odd_multiples(if self.signs.1 { b2 } else { -b2 });
+
+        let mut xa = G1Projective::ZERO;
+        for &(d1, d2) in self.digits.iter().rev() {
+            xa.double_in_place();
+            add_digit(&mut xa, &table1, d1);
+            add_digit(&mut xa, &table2, d2);
+        }
+        (ct.1 + xa).into_affine()
+    }
+}
+
+/// Compute the odd multiples P, 3P, ..., (2^(w-1) - 1)P of a point P.
+fn odd_multiples(p: G1Projective) -> [G1Projective; 1 << (WINDOW - 2)] {
+    let double = p.double();
+    let mut table = [p; 1 << (WINDOW - 2)];
+    for i in 1..table.len() {
+        table[i] = table[i - 1] + double;
+    }
+    table
+}
+
+/// Add the multiple of the point of `table` for the odd (or zero) digit `d`.
+fn add_digit(acc: &mut G1Projective, table: &[G1Projective], d: i64) {
+    if d > 0 {
+        *acc += table[(d / 2) as usize];
+    } else if d < 0 {
+        *acc -= table[(-d / 2) as usize];
+    }
+}
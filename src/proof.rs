@@ -0,0 +1,586 @@
+//! Non-interactive zero-knowledge proofs about ciphertexts, made non-interactive with the
+//! Fiat-Shamir transform.
+//!
+//! The proof methods of [`EncryptKey`] and [`DecryptKey`] derive their challenges with the
+//! hash function `D`, any [`Digest`] (e.g. SHA-256, SHA-512 or Keccak-256), chosen with a
+//! turbofish such as `prove_decrypt_sigma::<Sha256, _>`. A proof only verifies with the
+//! hash function it was created with. The wrappers [`PublicKey`](crate::PublicKey) and
+//! [`SecretKey`](crate::SecretKey) always use SHA-256.
+//!
+//! The decryption and same-secret proofs are also available as interactive protocols
+//! with external challenges, see [`sigma`](crate::sigma).
+
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use sha2::Digest;
+
+use super::{
+    ciphertext::Ciphertext,
+    decrypt::DecryptKey,
+    encrypt::EncryptKey,
+    pedersen::PedersenParams,
+    sigma::{DleqStatement, SigmaCommitment, SigmaProver},
+};
+
+type ScalarField<G> = <G as PrimeGroup>::ScalarField;
+
+/// The domain separation tag of the one-of-N proofs.
+const ONE_OF_MANY_DOMAIN: &[u8] = b"bls-elgamal/one-of-many";
+/// The domain separation tag of the decryption proofs.
+const DECRYPT_DOMAIN: &[u8] = b"bls-elgamal/decrypt";
+/// The domain separation tag of the batch decryption proofs.
+const BATCH_DECRYPT_DOMAIN: &[u8] = b"bls-elgamal/decrypt-batch";
+/// The domain separation tag of the weights of the batch decryption proofs.
+const BATCH_WEIGHTS_DOMAIN: &[u8] = b"bls-elgamal/decrypt-batch-weights";
+/// The domain separation tag of the proofs of encryptions of the identity.
+const ZERO_DOMAIN: &[u8] = b"bls-elgamal/zero";
+/// The domain separation tag of the same-secret proofs.
+const SAME_SECRET_DOMAIN: &[u8] = b"bls-elgamal/same-secret";
+/// The domain separation tag of the commitment-matching proofs.
+const MATCH_DOMAIN: &[u8] = b"bls-elgamal/match-commitment";
+
+/// A proof that a ciphertext (a, b) encrypts one of two candidate messages, without
+/// revealing which (a disjunctive Chaum-Pedersen proof).
+///
+/// For each candidate m_i it holds a challenge c_i and a response z_i, such that the
+/// challenges sum to the Fiat-Shamir challenge of the commitments z_iG - c_ia and
+/// z_iY - c_i(b - m_i). Only the branch of the actual message is proven honestly, the
+/// other is simulated.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct OrProof<G: CurveGroup> {
+    challenges: [ScalarField<G>; 2],
+    responses: [ScalarField<G>; 2],
+}
+
+/// A proof that a ciphertext encrypts one of N candidate messages, without revealing
+/// which. It generalizes [`OrProof`], simulating N - 1 branches.
+///
+/// The proof holds a challenge and a response per candidate, so its size grows linearly
+/// with N: 2N scalars, i.e. 64N bytes compressed for BLS12-381.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct OneOfManyProof<G: CurveGroup> {
+    challenges: Vec<ScalarField<G>>,
+    responses: Vec<ScalarField<G>>,
+}
+
+/// A proof that a ciphertext (a, b) decrypts to a message m under the public key Y, i.e.
+/// that log_G(Y) = log_a(b - m) (a Chaum-Pedersen proof of discrete log equality).
+///
+/// It consists of the challenge c and the response z, so it verifies with four scalar
+/// multiplications and no pairings.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigmaProof<G: CurveGroup> {
+    challenge: ScalarField<G>,
+    response: ScalarField<G>,
+}
+
+/// A proof that ciphertexts (a_i, b_i) decrypt to messages m_i under the public key Y,
+/// with a single proof of discrete log equality for all of them.
+///
+/// The ciphertexts are combined with weights w_i derived by hashing the key, the
+/// ciphertexts and the messages, and the proof shows log_G(Y) = log_A(B) for
+/// A = sum w_i a_i and B = sum w_i (b_i - m_i). A wrong message passes only if the
+/// weights cancel its error, which happens with negligible probability. Like
+/// [`SigmaProof`], it consists of a challenge and a response, whatever the number of
+/// ciphertexts.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchDecryptionProof<G: CurveGroup> {
+    challenge: ScalarField<G>,
+    response: ScalarField<G>,
+}
+
+/// A proof that two public keys Y1 = xG1 and Y2 = xG2 under different generators share
+/// the same secret x, i.e. that log_G1(Y1) = log_G2(Y2), without revealing x.
+///
+/// Like [`SigmaProof`], it consists of a challenge and a response.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SameSecretProof<G: CurveGroup> {
+    challenge: ScalarField<G>,
+    response: ScalarField<G>,
+}
+
+/// A proof that a ciphertext (a, b) encrypts the identity under the public key Y, i.e.
+/// that a = rG and b = rY for the same r (a proof of discrete log equality), e.g. for a
+/// rerandomization token of a mix-net, which must not change the message it is added to.
+///
+/// Like [`SigmaProof`], it consists of a challenge and a response.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ZeroProof<G: CurveGroup> {
+    challenge: ScalarField<G>,
+    response: ScalarField<G>,
+}
+
+/// A proof that a ciphertext (a, b) = (rG, vP + rY) encrypts the same scalar v as the
+/// Pedersen commitment C = vP + sH, where P and H are the bases of the
+/// [`PedersenParams`], e.g. for verifiable escrow.
+///
+/// It proves knowledge of (v, r, s) with a single challenge over the three relations,
+/// and consists of the challenge and a response per witness, i.e. four scalars.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MatchProof<G: CurveGroup> {
+    challenge: ScalarField<G>,
+    responses: [ScalarField<G>; 3],
+}
+
+impl<G: CurveGroup> DecryptKey<G> {
+    /// Decrypt the ciphertext `ct` and prove that the result is its message.
+    pub fn prove_decrypt_sigma<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G>,
+        rng: &mut R,
+    ) -> SigmaProof<G> {
+        let (challenge, response) =
+            prove_dleq::<D, _, _>(DECRYPT_DOMAIN, self.decrypt_prover(ct), rng);
+        SigmaProof {
+            challenge,
+            response,
+        }
+    }
+
+    /// Decrypt the ciphertexts `cts` and prove that the results are their messages, with
+    /// one proof for all of them (see [`BatchDecryptionProof`]). The proof does not
+    /// verify for an empty batch.
+    pub fn prove_decryption_batch<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        cts: &[Ciphertext<G>],
+        rng: &mut R,
+    ) -> BatchDecryptionProof<G> {
+        let ms = self.decrypt_batch(cts);
+        let statement = batch_decrypt_statement::<D, _>(&self.encrypt_key, cts, &ms);
+        let prover = SigmaProver::new(statement, self.secret);
+        let (challenge, response) = prove_dleq::<D, _, _>(BATCH_DECRYPT_DOMAIN, prover, rng);
+        BatchDecryptionProof {
+            challenge,
+            response,
+        }
+    }
+
+    /// Prove that this key and the key with the same secret under the generator
+    /// `generator` (i.e. `DecryptKey::new(generator, x)`) share the secret.
+    pub fn prove_same_secret<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        generator: G::Affine,
+        rng: &mut R,
+    ) -> SameSecretProof<G> {
+        let (challenge, response) =
+            prove_dleq::<D, _, _>(SAME_SECRET_DOMAIN, self.same_secret_prover(generator), rng);
+        SameSecretProof {
+            challenge,
+            response,
+        }
+    }
+}
+
+impl<G: CurveGroup> EncryptKey<G> {
+    /// Verify that the ciphertext `ct` decrypts to the message `m` under this key.
+    pub fn verify_decrypt_sigma<D: Digest>(
+        &self,
+        ct: &Ciphertext<G>,
+        m: G::Affine,
+        proof: &SigmaProof<G>,
+    ) -> bool {
+        let statement = self.decrypt_statement(ct, m);
+        verify_dleq::<D, _>(DECRYPT_DOMAIN, statement, proof.challenge, proof.response)
+    }
+
+    /// Verify that the ciphertexts `cts` decrypt to the messages `ms` under this key, in
+    /// order. Returns `false` if the numbers of ciphertexts and messages differ or there
+    /// are none.
+    pub fn verify_decryption_batch<D: Digest>(
+        &self,
+        cts: &[Ciphertext<G>],
+        ms: &[G::Affine],
+        proof: &BatchDecryptionProof<G>,
+    ) -> bool {
+        if cts.is_empty() || cts.len() != ms.len() {
+            return false;
+        }
+        let statement = batch_decrypt_statement::<D, _>(self, cts, ms);
+        verify_dleq::<D, _>(
+            BATCH_DECRYPT_DOMAIN,
+            statement,
+            proof.challenge,
+            proof.response,
+        )
+    }
+
+    /// Verify that this key and `other` share the same secret, under their respective
+    /// generators.
+    pub fn verify_same_secret<D: Digest>(
+        &self,
+        other: &EncryptKey<G>,
+        proof: &SameSecretProof<G>,
+    ) -> bool {
+        let statement = self.same_secret_statement(other);
+        verify_dleq::<D, _>(
+            SAME_SECRET_DOMAIN,
+            statement,
+            proof.challenge,
+            proof.response,
+        )
+    }
+
+    /// Encrypt the identity with fresh randomness r and prove it, see [`ZeroProof`].
+    /// Returns the ciphertext (rG, rY), r and the proof.
+    pub fn encrypt_zero_proved<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, ScalarField<G>, ZeroProof<G>) {
+        let r = ScalarField::<G>::rand(rng);
+        let ct = self.encrypt(G::Affine::zero(), r);
+        let prover = SigmaProver::new(self.zero_statement(&ct), r);
+        let (challenge, response) = prove_dleq::<D, _, _>(ZERO_DOMAIN, prover, rng);
+        (
+            ct,
+            r,
+            ZeroProof {
+                challenge,
+                response,
+            },
+        )
+    }
+
+    /// Verify that the ciphertext `ct` encrypts the identity under this key.
+    pub fn verify_zero<D: Digest>(&self, ct: &Ciphertext<G>, proof: &ZeroProof<G>) -> bool {
+        verify_dleq::<D, _>(
+            ZERO_DOMAIN,
+            self.zero_statement(ct),
+            proof.challenge,
+            proof.response,
+        )
+    }
+
+    /// The statement that `ct` = (a, b) encrypts the identity: a = rG and b = rY.
+    fn zero_statement(&self, ct: &Ciphertext<G>) -> DleqStatement<G> {
+        DleqStatement::new(self.generator, ct.0, self.y, ct.1)
+    }
+
+    /// Prove that the ciphertext `ct` = (rG, vP + rY), created with randomness `r_enc`,
+    /// encrypts the scalar `v` committed to in `commitment` = vP + sH with the blinding
+    /// `r_com`, where P and H are the bases of `params`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_encryption_matches_commitment<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        params: &PedersenParams<G>,
+        commitment: G::Affine,
+        ct: &Ciphertext<G>,
+        v: ScalarField<G>,
+        r_enc: ScalarField<G>,
+        r_com: ScalarField<G>,
+        rng: &mut R,
+    ) -> MatchProof<G> {
+        let [w_v, w_enc, w_com] = [(); 3].map(|_| ScalarField::<G>::rand(rng));
+        let commitments = [
+            self.generator * w_enc,
+            params.g * w_v + self.y * w_enc,
+            params.g * w_v + params.h * w_com,
+        ];
+        let c = match_challenge::<D, _>(self, params, commitment, ct, commitments);
+        MatchProof {
+            challenge: c,
+            responses: [w_v + c * v, w_enc + c * r_enc, w_com + c * r_com],
+        }
+    }
+
+    /// Verify that the ciphertext `ct` encrypts the scalar committed to in `commitment`
+    /// under the bases of `params`.
+    pub fn verify_encryption_matches_commitment<D: Digest>(
+        &self,
+        params: &PedersenParams<G>,
+        commitment: G::Affine,
+        ct: &Ciphertext<G>,
+        proof: &MatchProof<G>,
+    ) -> bool {
+        let (c, [z_v, z_enc, z_com]) = (proof.challenge, proof.responses);
+        let commitments = [
+            self.generator * z_enc - ct.0 * c,
+            params.g * z_v + self.y * z_enc - ct.1 * c,
+            params.g * z_v + params.h * z_com - commitment * c,
+        ];
+        match_challenge::<D, _>(self, params, commitment, ct, commitments) == c
+    }
+
+    /// Prove that the ciphertext `ct`, created with randomness `r`, encrypts
+    /// `candidates[1]` if `actual` is true and `candidates[0]` otherwise, without
+    /// revealing which.
+    pub fn prove_encryption_of_one_of<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G>,
+        r: ScalarField<G>,
+        actual: bool,
+        candidates: [G::Affine; 2],
+        rng: &mut R,
+    ) -> OrProof<G> {
+        let (challenges, responses) =
+            prove_one_of_many::<D, _, _>(self, ct, r, actual as usize, &candidates, rng);
+        OrProof {
+            challenges: [challenges[0], challenges[1]],
+            responses: [responses[0], responses[1]],
+        }
+    }
+
+    /// Verify that the ciphertext `ct` encrypts one of the two `candidates`.
+    pub fn verify_encryption_of_one_of<D: Digest>(
+        &self,
+        ct: &Ciphertext<G>,
+        candidates: [G::Affine; 2],
+        proof: &OrProof<G>,
+    ) -> bool {
+        verify_one_of_many::<D, _>(self, ct, &candidates, &proof.challenges, &proof.responses)
+    }
+
+    /// Prove that the ciphertext `ct`, created with randomness `r`, encrypts
+    /// `candidates[actual_index]`, without revealing the index. The Fiat-Shamir challenge
+    /// binds all candidates in their order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `actual_index` is not an index of `candidates`.
+    pub fn prove_encryption_of_one_of_many<D: Digest, R: CryptoRng + RngCore>(
+        &self,
+        ct: &Ciphertext<G>,
+        r: ScalarField<G>,
+        actual_index: usize,
+        candidates: &[G::Affine],
+        rng: &mut R,
+    ) -> OneOfManyProof<G> {
+        assert!(
+            actual_index < candidates.len(),
+            "actual index {actual_index} is out of {} candidates",
+            candidates.len()
+        );
+        let (challenges, responses) =
+            prove_one_of_many::<D, _, _>(self, ct, r, actual_index, candidates, rng);
+        OneOfManyProof {
+            challenges,
+            responses,
+        }
+    }
+
+    /// Verify that the ciphertext `ct` encrypts one of the `candidates`.
+    pub fn verify_encryption_of_one_of_many<D: Digest>(
+        &self,
+        ct: &Ciphertext<G>,
+        candidates: &[G::Affine],
+        proof: &OneOfManyProof<G>,
+    ) -> bool {
+        verify_one_of_many::<D, _>(self, ct, candidates, &proof.challenges, &proof.responses)
+    }
+}
+
+/// The Fiat-Shamir challenge of a commitment-matching proof, binding the key, the
+/// Pedersen bases, the commitment, the ciphertext and the prover's commitments.
+fn match_challenge<D: Digest, G: CurveGroup>(
+    key: &EncryptKey<G>,
+    params: &PedersenParams<G>,
+    commitment: G::Affine,
+    ct: &Ciphertext<G>,
+    commitments: [G; 3],
+) -> ScalarField<G> {
+    let mut points = vec![
+        key.generator,
+        key.y,
+        params.g,
+        params.h,
+        commitment.into_group(),
+        ct.0,
+        ct.1,
+    ];
+    points.extend_from_slice(&commitments);
+
+    let mut data = Vec::new();
+    G::normalize_batch(&points)
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    hash_to_scalar::<D, _>(MATCH_DOMAIN, &data)
+}
+
+/// Run the Sigma protocol of `prover` with the Fiat-Shamir challenge of its commitment.
+/// Returns the challenge and the response.
+fn prove_dleq<D: Digest, G: CurveGroup, R: CryptoRng + RngCore>(
+    domain: &[u8],
+    mut prover: SigmaProver<G>,
+    rng: &mut R,
+) -> (ScalarField<G>, ScalarField<G>) {
+    let commitment = prover.commit(rng);
+    let c = dleq_challenge::<D, _>(domain, prover.statement(), &commitment);
+    let response = prover.respond(c).expect("the prover has committed");
+    (c, response.0)
+}
+
+/// Verify the challenge `c` and the response `z` of a proof of [`prove_dleq`], by
+/// recomputing the commitments z g1 - c h1 and z g2 - c h2.
+fn verify_dleq<D: Digest, G: CurveGroup>(
+    domain: &[u8],
+    statement: DleqStatement<G>,
+    c: ScalarField<G>,
+    z: ScalarField<G>,
+) -> bool {
+    let [g1, h1, g2, h2] = statement.points;
+    let commitment = SigmaCommitment(g1 * z - h1 * c, g2 * z - h2 * c);
+    dleq_challenge::<D, _>(domain, &statement, &commitment) == c
+}
+
+/// The Fiat-Shamir challenge of a discrete log equality proof.
+fn dleq_challenge<D: Digest, G: CurveGroup>(
+    domain: &[u8],
+    statement: &DleqStatement<G>,
+    commitment: &SigmaCommitment<G>,
+) -> ScalarField<G> {
+    let mut data = Vec::new();
+    G::normalize_batch(&[statement.points.as_slice(), &[commitment.0, commitment.1]].concat())
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    hash_to_scalar::<D, _>(domain, &data)
+}
+
+/// The statement [G, Y, A, B] of a batch decryption proof, with A = sum w_i a_i and
+/// B = sum w_i (b_i - m_i) for the weights w_i. The weights are the hashes of a seed and
+/// their index, where the seed is the hash of the key, the ciphertexts and the messages.
+fn batch_decrypt_statement<D: Digest, G: CurveGroup>(
+    key: &EncryptKey<G>,
+    cts: &[Ciphertext<G>],
+    ms: &[G::Affine],
+) -> DleqStatement<G> {
+    let points = cts
+        .iter()
+        .zip(ms)
+        .flat_map(|(ct, m)| [ct.0, ct.1 - m])
+        .collect::<Vec<_>>();
+    let points = G::normalize_batch(&points);
+
+    let mut data = Vec::new();
+    G::normalize_batch(&[key.generator, key.y])
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    points
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    let seed = D::new()
+        .chain_update(BATCH_WEIGHTS_DOMAIN)
+        .chain_update(data)
+        .finalize();
+    let weights = (0..cts.len() as u64)
+        .map(|i| {
+            hash_to_scalar::<D, _>(
+                BATCH_WEIGHTS_DOMAIN,
+                &[&seed[..], &i.to_le_bytes()].concat(),
+            )
+        })
+        .collect::<Vec<ScalarField<G>>>();
+
+    let (a, b): (Vec<_>, Vec<_>) = points.chunks_exact(2).map(|p| (p[0], p[1])).unzip();
+    DleqStatement::new(
+        key.generator,
+        key.y,
+        G::msm_unchecked(&a, &weights),
+        G::msm_unchecked(&b, &weights),
+    )
+}
+
+/// Prove that `ct` encrypts `candidates[index]`, simulating the transcripts of all other
+/// candidates. Returns the challenges and responses of all branches.
+fn prove_one_of_many<D: Digest, G: CurveGroup, R: CryptoRng + RngCore>(
+    key: &EncryptKey<G>,
+    ct: &Ciphertext<G>,
+    r: ScalarField<G>,
+    index: usize,
+    candidates: &[G::Affine],
+    rng: &mut R,
+) -> (Vec<ScalarField<G>>, Vec<ScalarField<G>>) {
+    let mut challenges = vec![ScalarField::<G>::zero(); candidates.len()];
+    let mut responses = vec![ScalarField::<G>::zero(); candidates.len()];
+    let mut commitments = Vec::with_capacity(2 * candidates.len());
+
+    let w = ScalarField::<G>::rand(rng);
+    for (i, m) in candidates.iter().enumerate() {
+        if i == index {
+            commitments.push(key.generator * w);
+            commitments.push(key.y * w);
+        } else {
+            let (c, z) = (ScalarField::<G>::rand(rng), ScalarField::<G>::rand(rng));
+            commitments.push(key.generator * z - ct.0 * c);
+            commitments.push(key.y * z - (ct.1 - m) * c);
+            challenges[i] = c;
+            responses[i] = z;
+        }
+    }
+
+    let c = one_of_many_challenge::<D, _>(key, ct, candidates, &commitments);
+    challenges[index] = c - challenges.iter().sum::<ScalarField<G>>();
+    responses[index] = w + challenges[index] * r;
+    (challenges, responses)
+}
+
+/// Verify the challenges and responses of a one-of-N proof.
+fn verify_one_of_many<D: Digest, G: CurveGroup>(
+    key: &EncryptKey<G>,
+    ct: &Ciphertext<G>,
+    candidates: &[G::Affine],
+    challenges: &[ScalarField<G>],
+    responses: &[ScalarField<G>],
+) -> bool {
+    if candidates.is_empty()
+        || challenges.len() != candidates.len()
+        || responses.len() != candidates.len()
+    {
+        return false;
+    }
+
+    let commitments = candidates
+        .iter()
+        .zip(challenges.iter().zip(responses))
+        .flat_map(|(m, (c, z))| [key.generator * z - ct.0 * c, key.y * z - (ct.1 - m) * c])
+        .collect::<Vec<_>>();
+
+    one_of_many_challenge::<D, _>(key, ct, candidates, &commitments)
+        == challenges.iter().sum::<ScalarField<G>>()
+}
+
+/// The Fiat-Shamir challenge of a one-of-N proof, binding the key, the ciphertext, every
+/// candidate and every commitment.
+fn one_of_many_challenge<D: Digest, G: CurveGroup>(
+    key: &EncryptKey<G>,
+    ct: &Ciphertext<G>,
+    candidates: &[G::Affine],
+    commitments: &[G],
+) -> ScalarField<G> {
+    let mut points = vec![key.generator, key.y, ct.0, ct.1];
+    points.extend(candidates.iter().map(|m| m.into_group()));
+    points.extend_from_slice(commitments);
+
+    let mut data = Vec::new();
+    (candidates.len() as u64)
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    G::normalize_batch(&points)
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector cannot fail");
+    hash_to_scalar::<D, _>(ONE_OF_MANY_DOMAIN, &data)
+}
+
+/// Hash `data` under the domain separation tag `domain` to a scalar with the hash
+/// function `D`. Hashes with an incrementing counter are concatenated to at least 64 bytes
+/// of output, which are reduced so that the result is (close to) uniform over the scalar
+/// field.
+pub(crate) fn hash_to_scalar<D: Digest, F: PrimeField>(domain: &[u8], data: &[u8]) -> F {
+    let mut wide = Vec::with_capacity(64);
+    let mut counter = 0u8;
+    while wide.len() < 64 {
+        wide.extend(
+            D::new()
+                .chain_update([domain.len() as u8])
+                .chain_update(domain)
+                .chain_update([counter])
+                .chain_update(data)
+                .finalize(),
+        );
+        counter += 1;
+    }
+    F::from_le_bytes_mod_order(&wide)
+}
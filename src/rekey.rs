@@ -0,0 +1,24 @@
+use ark_ec::{CurveGroup, PrimeGroup};
+
+use super::ciphertext::Ciphertext;
+
+/// A re-encryption key to transform a ciphertext under one key into a ciphertext of the
+/// same message under another key with the same generator, without decrypting it.
+///
+/// It is the difference of the two secrets, δ = x' - x, created by
+/// [`DecryptKey::rekey`](crate::decrypt::DecryptKey::rekey). Anyone holding it and one
+/// of the two secrets can derive the other, so it should only be given to a party
+/// trusted by both key holders (e.g. a mix-net node in between them).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ReKey<G: CurveGroup> {
+    pub(crate) delta: <G as PrimeGroup>::ScalarField, // x' - x
+}
+
+impl<G: CurveGroup> ReKey<G> {
+    /// Re-encrypt a ciphertext (a, b) to get (a, b + δa), which decrypts under the new
+    /// key to the same message.
+    pub fn reencrypt(&self, ct: Ciphertext<G>) -> Ciphertext<G> {
+        count_ops!(SCALAR_MULS);
+        Ciphertext(ct.0, ct.1 + ct.0 * self.delta)
+    }
+}
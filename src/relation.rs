@@ -0,0 +1,22 @@
+//! The native reference computation of the encryption relation, for checking the witness
+//! of circuits that prove encryption, e.g. SNARK gadgets.
+//!
+//! A ciphertext (a, b) of the message m under the key (G, Y) with randomness r satisfies
+//! a = rG and b = m + rY. A gadget constraining these equations should agree with
+//! [`expected_a`] and [`expected_b`] on every witness, and the ciphertexts of
+//! [`EncryptKey::encrypt`](crate::EncryptKey::encrypt) are the same points in projective
+//! form.
+
+use ark_ec::{AffineRepr, CurveGroup};
+
+/// Compute the first component a = rG of a ciphertext with randomness `r` under a key
+/// with the generator `generator`.
+pub fn expected_a<A: AffineRepr>(generator: A, r: A::ScalarField) -> A {
+    (generator * r).into_affine()
+}
+
+/// Compute the second component b = m + rY of a ciphertext of the message `m` with
+/// randomness `r` under the public key `y`.
+pub fn expected_b<A: AffineRepr>(y: A, r: A::ScalarField, m: A) -> A {
+    (y * r + m).into_affine()
+}
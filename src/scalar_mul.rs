@@ -0,0 +1,38 @@
+//! A hook for the scalar multiplications of encryption and decryption, e.g. to offload
+//! them to a crypto accelerator, an HSM or a GPU.
+
+use ark_ec::{CurveGroup, PrimeGroup};
+
+/// A backend computing the scalar multiplications rG and rY of encryption and xa of
+/// decryption, which dominate their cost.
+///
+/// These methods of [`EncryptKey`](crate::EncryptKey) and
+/// [`DecryptKey`](crate::DecryptKey), and their counterparts on
+/// [`PublicKey`](crate::PublicKey) and [`SecretKey`](crate::SecretKey), take a backend:
+///
+/// - `encrypt_with`, `encrypt_projective_with`, `rerandomize_with` and
+///   `rerandomize_and_rekey_with` of the encryption key,
+/// - `decrypt_with`, `decrypt_projective_with`, `decrypt_batch_with`,
+///   `decrypt_scalar_with`, `decrypt_signed_with` and `decrypt_value_with` of the
+///   decryption key.
+///
+/// Their counterparts without the suffix, and the methods built on those (e.g.
+/// `encrypt_scalar` or `decrypt_into`), use [`SoftwareScalarMul`]. All other scalar
+/// multiplications, e.g. of key generation, proofs, labels,
+/// [`EncryptKey::encrypt_reusing_ephemeral`](crate::EncryptKey::encrypt_reusing_ephemeral)
+/// and [`DecryptKey::decrypt_try_keys`](crate::DecryptKey::decrypt_try_keys), are
+/// always computed by arkworks.
+pub trait ScalarMul<G: CurveGroup> {
+    /// Compute `scalar` times `base`.
+    fn mul(&self, base: &G, scalar: &<G as PrimeGroup>::ScalarField) -> G;
+}
+
+/// The default backend, the scalar multiplication of arkworks.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SoftwareScalarMul;
+
+impl<G: CurveGroup> ScalarMul<G> for SoftwareScalarMul {
+    fn mul(&self, base: &G, scalar: &<G as PrimeGroup>::ScalarField) -> G {
+        *base * scalar
+    }
+}
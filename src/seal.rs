@@ -0,0 +1,227 @@
+//! Group encryption of arbitrary bytes: the payload is encrypted once with
+//! ChaCha20-Poly1305, and its key is wrapped for every recipient with ElGamal.
+//!
+//! The key is the SHA-256 hash of a random point, which is encrypted to all recipients
+//! at once as a [`MultiCiphertext`]. Any recipient decrypts its share of the
+//! [`MultiCiphertext`] to get the point, derives the key and opens the payload.
+//!
+//! [`GroupSealed::to_bytes`] serializes a sealed message as:
+//!
+//! - the number of recipients n, as a u32 in little-endian,
+//! - the n + 1 compressed points (48 bytes each) a, b_1, ..., b_n of the wrapped key,
+//! - the length of the payload, as a u32 in little-endian,
+//! - the encrypted payload, followed by its 16-byte tag.
+
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use sha2::{Digest, Sha256};
+
+use super::{Error, Fr, G1Affine, MultiCiphertext, PublicKey, SecretKey, G1};
+
+/// The domain separation tag of the derivation of the payload key.
+const SEAL_DOMAIN: &[u8] = b"bls-elgamal/seal";
+
+/// The size of a compressed G1 point.
+const POINT_SIZE: usize = 48;
+/// The size of a length prefix.
+const LEN_SIZE: usize = 4;
+/// The size of the ChaCha20-Poly1305 tag at the end of the payload.
+const TAG_SIZE: usize = 16;
+
+/// A payload sealed to several recipients by [`PublicKey::seal_to_many`], opened by
+/// [`SecretKey::open`] with the key of any of them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupSealed {
+    /// The point the payload key is derived from, encrypted to every recipient.
+    wrapped_key: MultiCiphertext<G1>,
+    /// The payload encrypted with ChaCha20-Poly1305, followed by its tag.
+    payload: Vec<u8>,
+}
+
+impl GroupSealed {
+    /// Get the number of recipients.
+    pub fn recipients(&self) -> usize {
+        self.wrapped_key.len()
+    }
+
+    /// Get the encrypted payload, which is the plaintext length plus a 16-byte tag.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl GroupSealed {
+    /// Serialize the sealed message in the layout of the [module](self) documentation,
+    /// parsed back by `GroupSealed::try_from`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of recipients or the length of the payload (including its
+    /// tag) does not fit in a u32, i.e. for payloads of 4 GiB or more.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let points = std::iter::once(self.wrapped_key.shared_a())
+            .chain(self.wrapped_key.iter().map(|ct| ct.1))
+            .collect::<Vec<_>>();
+        let mut bytes =
+            Vec::with_capacity(2 * LEN_SIZE + points.len() * POINT_SIZE + self.payload.len());
+        bytes.extend_from_slice(
+            &u32::try_from(self.wrapped_key.len())
+                .expect("the number of recipients fits in a u32")
+                .to_le_bytes(),
+        );
+        count_ops!(NORMALIZATIONS);
+        for point in G1::normalize_batch(&points) {
+            point
+                .serialize_compressed(&mut bytes)
+                .expect("serializing to a vector cannot fail");
+        }
+        bytes.extend_from_slice(
+            &u32::try_from(self.payload.len())
+                .expect("the payload is shorter than 4 GiB")
+                .to_le_bytes(),
+        );
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for GroupSealed {
+    type Error = Error;
+
+    /// Parse a sealed message serialized by [`GroupSealed::to_bytes`]. Returns
+    /// [`Error::InvalidLength`] if the bytes are truncated or followed by trailing bytes,
+    /// or [`Error::InvalidEncoding`] if there are no recipients, a point is invalid or the
+    /// payload is shorter than its tag.
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let n = read_len(bytes, 0)?;
+        if n == 0 {
+            return Err(Error::InvalidEncoding);
+        }
+        let points_end = n
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(POINT_SIZE))
+            .and_then(|size| size.checked_add(LEN_SIZE))
+            .ok_or(Error::InvalidEncoding)?;
+        let payload_len = read_len(bytes, points_end)?;
+        let payload_start = points_end + LEN_SIZE;
+        let expected = payload_start
+            .checked_add(payload_len)
+            .ok_or(Error::InvalidEncoding)?;
+        if bytes.len() != expected {
+            return Err(Error::InvalidLength {
+                expected,
+                got: bytes.len(),
+            });
+        }
+        if payload_len < TAG_SIZE {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let mut points = bytes[LEN_SIZE..points_end]
+            .chunks_exact(POINT_SIZE)
+            .map(|chunk| {
+                G1Affine::deserialize_compressed(chunk)
+                    .map(G1::from)
+                    .map_err(|_| Error::InvalidEncoding)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bs = points.split_off(1);
+        Ok(GroupSealed {
+            wrapped_key: MultiCiphertext::from_parts(points[0], bs)?,
+            payload: bytes[payload_start..].to_vec(),
+        })
+    }
+}
+
+impl TryFrom<Vec<u8>> for GroupSealed {
+    type Error = Error;
+
+    /// See the impl of `TryFrom<&[u8]>`.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl PublicKey {
+    /// Seal `plaintext` to every key of `recipients`, encrypting the payload once and
+    /// wrapping its key for each recipient.
+    ///
+    /// Returns [`Error::EmptyInput`] if there are no recipients, or
+    /// [`Error::GeneratorMismatch`] if they do not share the same generator.
+    pub fn seal_to_many<R: CryptoRng + RngCore>(
+        recipients: &[PublicKey],
+        plaintext: &[u8],
+        rng: &mut R,
+    ) -> Result<GroupSealed, Error> {
+        let keys = recipients.iter().map(|k| k.inner).collect::<Vec<_>>();
+        let point = G1Affine::rand(rng);
+        let wrapped_key = MultiCiphertext::encrypt(&keys, point, Fr::rand(rng))?;
+        let payload = cipher(point)
+            .encrypt(&Nonce::default(), plaintext)
+            .expect("encrypting into a vector cannot fail");
+        Ok(GroupSealed {
+            wrapped_key,
+            payload,
+        })
+    }
+}
+
+impl SecretKey {
+    /// Open a payload sealed by [`PublicKey::seal_to_many`] to this key. Each wrapped key
+    /// is tried in turn, with a single scalar multiplication for all of them.
+    ///
+    /// Returns [`Error::AuthenticationFailed`] if the payload does not open with any of
+    /// them, i.e. this key is not a recipient or the payload was tampered with.
+    pub fn open(&self, sealed: &GroupSealed) -> Result<Vec<u8>, Error> {
+        count_ops!(SCALAR_MULS);
+        let shared = sealed.wrapped_key.shared_a() * self.inner.secret();
+        let points = sealed
+            .wrapped_key
+            .iter()
+            .map(|ct| ct.1 - shared)
+            .collect::<Vec<_>>();
+        count_ops!(NORMALIZATIONS);
+        G1::normalize_batch(&points)
+            .into_iter()
+            .find_map(|point| {
+                cipher(point)
+                    .decrypt(&Nonce::default(), sealed.payload.as_slice())
+                    .ok()
+            })
+            .ok_or(Error::AuthenticationFailed)
+    }
+}
+
+/// The cipher keyed by the hash of `point`. Every point is random and keys a single
+/// payload, so the nonce is fixed. Changing the wrapped key of a recipient changes the
+/// point it decrypts to, so the tag also authenticates the wrapped key.
+fn cipher(point: G1Affine) -> ChaCha20Poly1305 {
+    let mut bytes = Vec::new();
+    point
+        .serialize_compressed(&mut bytes)
+        .expect("serializing to a vector cannot fail");
+    let key = Sha256::new()
+        .chain_update(SEAL_DOMAIN)
+        .chain_update(bytes)
+        .finalize();
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+/// Read the little-endian u32 length prefix at `offset` of `bytes`, or return
+/// [`Error::InvalidLength`] if the bytes end before it.
+fn read_len(bytes: &[u8], offset: usize) -> Result<usize, Error> {
+    let end = offset + LEN_SIZE;
+    let prefix = bytes.get(offset..end).ok_or(Error::InvalidLength {
+        expected: end,
+        got: bytes.len(),
+    })?;
+    Ok(u32::from_le_bytes(prefix.try_into().expect("the prefix has 4 bytes")) as usize)
+}
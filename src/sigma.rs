@@ -0,0 +1,145 @@
+//! The interactive Sigma protocols behind the decryption and same-secret proofs of
+//! [`proof`](crate::proof), with the three moves exposed separately.
+//!
+//! Both proofs are proofs of discrete log equality: for a [`DleqStatement`]
+//! [g1, h1, g2, h2], the prover knows x such that h1 = x g1 and h2 = x g2.
+//!
+//! 1. The prover sends a [`SigmaCommitment`] from [`SigmaProver::commit`].
+//! 2. The verifier (or an outer protocol, e.g. an OR-composition or an MPC) picks a
+//!    challenge.
+//! 3. The prover sends a [`SigmaResponse`] from [`SigmaProver::respond`], and the verifier
+//!    checks the transcript with [`DleqStatement::verify`].
+//!
+//! The non-interactive proofs, e.g. [`SigmaProof`](crate::SigmaProof), run the same
+//! protocol with the challenge derived by hashing the statement and the commitment.
+
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+
+use super::{ciphertext::Ciphertext, decrypt::DecryptKey, encrypt::EncryptKey};
+
+type ScalarField<G> = <G as PrimeGroup>::ScalarField;
+
+/// The statement [g1, h1, g2, h2] of a discrete log equality proof, i.e. that
+/// log_g1(h1) = log_g2(h2).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DleqStatement<G: CurveGroup> {
+    pub(crate) points: [G; 4],
+}
+
+/// The first move of the prover: the commitments w g1 and w g2 to a random nonce w.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigmaCommitment<G: CurveGroup>(pub G, pub G);
+
+/// The last move of the prover: the response z = w + cx to the challenge c.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigmaResponse<G: CurveGroup>(pub ScalarField<G>);
+
+/// The prover of a [`DleqStatement`], holding the secret x and the nonce of its
+/// commitment.
+///
+/// [`SigmaProver::respond`] consumes the prover, so a nonce never answers two
+/// challenges, which would reveal x.
+#[derive(Clone)]
+pub struct SigmaProver<G: CurveGroup> {
+    statement: DleqStatement<G>,
+    secret: ScalarField<G>,
+    nonce: Option<ScalarField<G>>,
+}
+
+impl<G: CurveGroup> DleqStatement<G> {
+    /// Create the statement [g1, h1, g2, h2].
+    pub fn new(g1: G, h1: G, g2: G, h2: G) -> Self {
+        Self {
+            points: [g1, h1, g2, h2],
+        }
+    }
+
+    /// Get the points [g1, h1, g2, h2] of the statement.
+    pub fn points(&self) -> [G; 4] {
+        self.points
+    }
+
+    /// Verify the transcript (`commitment`, `challenge`, `response`), i.e. that
+    /// z g1 = t1 + c h1 and z g2 = t2 + c h2.
+    pub fn verify(
+        &self,
+        commitment: &SigmaCommitment<G>,
+        challenge: ScalarField<G>,
+        response: &SigmaResponse<G>,
+    ) -> bool {
+        let [g1, h1, g2, h2] = self.points;
+        let z = response.0;
+        g1 * z == commitment.0 + h1 * challenge && g2 * z == commitment.1 + h2 * challenge
+    }
+}
+
+impl<G: CurveGroup> SigmaProver<G> {
+    /// Create a prover of `statement` with the secret `x`. The secret is not checked
+    /// against the statement; a wrong secret gives transcripts that do not verify.
+    pub fn new(statement: DleqStatement<G>, x: ScalarField<G>) -> Self {
+        Self {
+            statement,
+            secret: x,
+            nonce: None,
+        }
+    }
+
+    /// Get the statement being proven.
+    pub fn statement(&self) -> &DleqStatement<G> {
+        &self.statement
+    }
+
+    /// Sample a nonce and commit to it. Committing again replaces the nonce.
+    pub fn commit<R: CryptoRng + RngCore>(&mut self, rng: &mut R) -> SigmaCommitment<G> {
+        let [g1, _, g2, _] = self.statement.points;
+        let w = ScalarField::<G>::rand(rng);
+        self.nonce = Some(w);
+        SigmaCommitment(g1 * w, g2 * w)
+    }
+
+    /// Respond to the challenge `challenge`, or return `None` if the prover has not
+    /// committed yet.
+    pub fn respond(self, challenge: ScalarField<G>) -> Option<SigmaResponse<G>> {
+        self.nonce
+            .map(|w| SigmaResponse(w + challenge * self.secret))
+    }
+}
+
+impl<G: CurveGroup> EncryptKey<G> {
+    /// Get the statement that the ciphertext `ct` decrypts to the message `m` under this
+    /// key: Y = xG and b - m = xa.
+    pub fn decrypt_statement(&self, ct: &Ciphertext<G>, m: G::Affine) -> DleqStatement<G> {
+        DleqStatement::new(self.generator, self.y, ct.0, ct.1 - m.into_group())
+    }
+
+    /// Get the statement that this key and `other` share the same secret under their
+    /// respective generators: Y1 = xG1 and Y2 = xG2.
+    pub fn same_secret_statement(&self, other: &EncryptKey<G>) -> DleqStatement<G> {
+        DleqStatement::new(self.generator, self.y, other.generator, other.y)
+    }
+}
+
+impl<G: CurveGroup> DecryptKey<G> {
+    /// Decrypt the ciphertext `ct` and create a prover of the statement that the result
+    /// is its message, see [`EncryptKey::decrypt_statement`].
+    pub fn decrypt_prover(&self, ct: &Ciphertext<G>) -> SigmaProver<G> {
+        let m = self.decrypt(*ct);
+        SigmaProver::new(self.encrypt_key.decrypt_statement(ct, m), self.secret)
+    }
+
+    /// Create a prover of the statement that this key and the key with the same secret
+    /// under the generator `generator` share the secret, see
+    /// [`EncryptKey::same_secret_statement`].
+    pub fn same_secret_prover(&self, generator: G::Affine) -> SigmaProver<G> {
+        let other = DecryptKey::new(generator, self.secret);
+        SigmaProver::new(
+            self.encrypt_key.same_secret_statement(&other.encrypt_key),
+            self.secret,
+        )
+    }
+}
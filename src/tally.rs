@@ -0,0 +1,148 @@
+use ark_ec::CurveGroup;
+
+use super::{
+    bsgs::{bsgs_max_sum, BsgsTable},
+    ciphertext::Ciphertext,
+    error::Error,
+};
+
+/// An accumulator for the homomorphic sum of a stream of ciphertexts.
+///
+/// The running sum is kept in projective form, so adding a ciphertext costs two point
+/// additions and nothing is normalized until [`Tally::finalize`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Tally<G: CurveGroup> {
+    sum: Ciphertext<G>,
+    count: usize,
+}
+
+impl<G: CurveGroup> Tally<G> {
+    /// Create an empty tally, i.e. an encryption of the identity with no randomness.
+    pub fn new() -> Self {
+        Self {
+            sum: Ciphertext::default(),
+            count: 0,
+        }
+    }
+
+    /// Add a ciphertext to the tally.
+    pub fn add(&mut self, ct: &Ciphertext<G>) {
+        self.sum.0 += ct.0;
+        self.sum.1 += ct.1;
+        self.count += 1;
+    }
+
+    /// Add a public plaintext `m` to the encrypted sum. It does not count as a
+    /// ciphertext in [`Tally::count`].
+    pub fn add_plaintext(&mut self, m: G::Affine) {
+        self.sum.1 += m;
+    }
+
+    /// Get the number of ciphertexts added to the tally.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Check that `table` can recover the sum of the tally if every added ciphertext
+    /// encrypts a value in `[0, per_value_max]`, or return [`Error::OutOfRange`] if the
+    /// sum could exceed the range of the table. Plaintexts added with
+    /// [`Tally::add_plaintext`] are not accounted for.
+    pub fn check_range(&self, per_value_max: u64, table: &BsgsTable<G>) -> Result<(), Error> {
+        if bsgs_max_sum(per_value_max, self.count) > table.max() {
+            return Err(Error::OutOfRange);
+        }
+        Ok(())
+    }
+
+    /// Get the ciphertext of the sum, with both points normalized.
+    pub fn finalize(&self) -> Ciphertext<G> {
+        count_ops!(NORMALIZATIONS);
+        let points = G::normalize_batch(&[self.sum.0, self.sum.1]);
+        Ciphertext(points[0].into(), points[1].into())
+    }
+}
+
+impl<G: CurveGroup> Default for Tally<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: CurveGroup> FromIterator<Ciphertext<G>> for Tally<G> {
+    /// Collect ciphertexts into a tally, e.g. `let tally: Tally<_> = cts.into_iter().collect()`.
+    fn from_iter<I: IntoIterator<Item = Ciphertext<G>>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), |mut tally, ct| {
+            tally.add(&ct);
+            tally
+        })
+    }
+}
+
+impl<'a, G: CurveGroup> FromIterator<&'a Ciphertext<G>> for Tally<G> {
+    /// Collect borrowed ciphertexts into a tally, e.g. `cts.iter().collect()`.
+    fn from_iter<I: IntoIterator<Item = &'a Ciphertext<G>>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), |mut tally, ct| {
+            tally.add(ct);
+            tally
+        })
+    }
+}
+
+/// An accumulator for ballots over a fixed number of candidates, where each ballot holds
+/// one ciphertext per candidate and the tally keeps one homomorphic sum per candidate.
+///
+/// As with [`Tally`], the sums are kept in projective form until
+/// [`BallotTally::finalize`], which normalizes all of them in one batch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BallotTally<G: CurveGroup> {
+    sums: Vec<Ciphertext<G>>,
+    count: usize,
+}
+
+impl<G: CurveGroup> BallotTally<G> {
+    /// Create an empty tally for `candidates` candidates.
+    pub fn new(candidates: usize) -> Self {
+        Self {
+            sums: vec![Ciphertext::default(); candidates],
+            count: 0,
+        }
+    }
+
+    /// Add a ballot, i.e. add the ciphertext `ballot[i]` to the sum of candidate i for
+    /// every candidate.
+    ///
+    /// Returns [`Error::LengthMismatch`] if the ballot does not have one ciphertext per
+    /// candidate, in which case the tally is unchanged.
+    pub fn add_ballot(&mut self, ballot: &[Ciphertext<G>]) -> Result<(), Error> {
+        if ballot.len() != self.sums.len() {
+            return Err(Error::LengthMismatch);
+        }
+        for (sum, ct) in self.sums.iter_mut().zip(ballot) {
+            sum.0 += ct.0;
+            sum.1 += ct.1;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Get the number of candidates.
+    pub fn candidates(&self) -> usize {
+        self.sums.len()
+    }
+
+    /// Get the number of ballots added to the tally.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the ciphertexts of the sums of the candidates, in order, with all points
+    /// normalized.
+    pub fn finalize(&self) -> Vec<Ciphertext<G>> {
+        let points: Vec<G> = self.sums.iter().flat_map(|ct| [ct.0, ct.1]).collect();
+        count_ops!(NORMALIZATIONS);
+        G::normalize_batch(&points)
+            .chunks_exact(2)
+            .map(|p| Ciphertext(p[0].into(), p[1].into()))
+            .collect()
+    }
+}
@@ -0,0 +1,112 @@
+use ark_ff::{BigInt, PrimeField};
+
+use super::error::Error;
+
+// the same type as `crate::Fr`, named directly to construct it from its Montgomery limbs
+use ark_bls12_381::Fr;
+
+/// The number of random bytes [`fr_from_random_bytes`] reduces into a scalar.
+pub const RANDOM_BYTES_SIZE: usize = 64;
+
+/// Convert an unsigned integer into a scalar field element (e.g. [`Fr`](crate::Fr)).
+pub fn fr_from_u64<F: PrimeField>(v: u64) -> F {
+    F::from(v)
+}
+
+/// Convert a signed integer into a scalar field element (e.g. [`Fr`](crate::Fr)).
+/// Negative values are mapped to their field negation, i.e. `-v` becomes `p - v`
+/// where `p` is the field modulus.
+pub fn fr_from_i64<F: PrimeField>(v: i64) -> F {
+    let abs = F::from(v.unsigned_abs());
+    if v < 0 {
+        -abs
+    } else {
+        abs
+    }
+}
+
+/// Reduce [`RANDOM_BYTES_SIZE`] uniformly random bytes into a scalar field element, e.g.
+/// randomness from a hardware RNG. The bytes are read as a little-endian integer and
+/// reduced modulo the field order; with 512 bits of input the result is statistically
+/// close to uniform (the bias is below 2^-256 for [`Fr`](crate::Fr)). Returns
+/// [`Error::InvalidRandomness`] if `bytes` has any other length.
+///
+/// The reduction is variable time. For the scalars of BLS12-381, use
+/// [`reduce_bytes_ct`] on secret bytes instead, as [`PublicKey`](crate::PublicKey) does.
+pub fn fr_from_random_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, Error> {
+    if bytes.len() != RANDOM_BYTES_SIZE {
+        return Err(Error::InvalidRandomness);
+    }
+    Ok(F::from_le_bytes_mod_order(bytes))
+}
+
+/// Reduce the bytes `bytes`, read as a little-endian integer, modulo the order of
+/// [`Fr`](crate::Fr) in constant time, i.e. with no branches or memory accesses that
+/// depend on their value (only on their length). Pass [`RANDOM_BYTES_SIZE`] (64) bytes
+/// for a result that is statistically close to uniform.
+///
+/// It reduces bit by bit with conditional subtractions by masking, and is much slower
+/// than [`PrimeField::from_le_bytes_mod_order`]. Reductions of public inputs, such as
+/// the Fiat-Shamir challenges of [`proof`](crate::proof), use the faster variable-time
+/// path.
+pub fn reduce_bytes_ct(bytes: &[u8]) -> Fr {
+    let modulus = Fr::MODULUS.0;
+    // shift in the bits from the most significant one, followed by 256 zero bits, to get
+    // the Montgomery form v 2^256 mod r directly, without a (variable-time) conversion
+    let bits = bytes
+        .iter()
+        .rev()
+        .flat_map(|byte| (0..8).rev().map(move |i| u64::from((byte >> i) & 1)))
+        .chain(std::iter::repeat_n(0, 256));
+
+    let mut acc = [0u64; 4];
+    for bit in bits {
+        // acc = 2 acc + bit, which is less than 2r < 2^256
+        let mut carry = bit;
+        for limb in acc.iter_mut() {
+            let next = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+
+        // subtract r, and keep the difference unless it borrowed
+        let mut diff = [0u64; 4];
+        let mut borrow = 0u64;
+        for i in 0..4 {
+            let (d, b1) = acc[i].overflowing_sub(modulus[i]);
+            let (d, b2) = d.overflowing_sub(borrow);
+            diff[i] = d;
+            borrow = u64::from(b1 | b2);
+        }
+        let keep = borrow.wrapping_neg();
+        for i in 0..4 {
+            acc[i] = (acc[i] & keep) | (diff[i] & !keep);
+        }
+    }
+    Fr::new_unchecked(BigInt(acc))
+}
+
+/// Reduce [`RANDOM_BYTES_SIZE`] secret random bytes into a scalar with
+/// [`reduce_bytes_ct`], or return [`Error::InvalidRandomness`] if `bytes` has any other
+/// length.
+pub(crate) fn fr_from_random_bytes_ct(bytes: &[u8]) -> Result<Fr, Error> {
+    if bytes.len() != RANDOM_BYTES_SIZE {
+        return Err(Error::InvalidRandomness);
+    }
+    Ok(reduce_bytes_ct(bytes))
+}
+
+/// Convert a scalar field element back into an unsigned integer. Returns `Some` only if
+/// the element is a small integer, i.e. its canonical representative is less than 2^64.
+pub fn fr_to_u64_checked<F: PrimeField>(f: &F) -> Option<u64> {
+    let bigint = f.into_bigint();
+    let limbs = bigint.as_ref();
+    limbs[1..].iter().all(|&l| l == 0).then_some(limbs[0])
+}
+
+/// Invert every scalar in place with Montgomery's batch inversion trick, i.e. with a
+/// single field inversion and about 3n multiplications instead of n inversions.
+/// Zeros have no inverse and are left unchanged.
+pub fn batch_invert<F: PrimeField>(scalars: &mut [F]) {
+    ark_ff::batch_inversion(scalars);
+}
@@ -0,0 +1,102 @@
+//! WebAssembly bindings over the BLS12-381 wrappers (enabled by the `wasm` feature).
+//!
+//! Keys and ciphertexts are passed as `Uint8Array`s of their serialized (compressed)
+//! forms, and messages as compressed G1 points. Randomness is supplied by the caller,
+//! e.g. from `crypto.getRandomValues`, so no RNG is needed inside WebAssembly.
+//!
+//! ```js
+//! import { keygen, publicKey, scalarMessage, encrypt, add, decrypt } from "bls_elgamal";
+//!
+//! const random = () => crypto.getRandomValues(new Uint8Array(64));
+//!
+//! const sk = keygen(random());
+//! const pk = publicKey(sk);
+//!
+//! // two "yes" votes
+//! const yes = scalarMessage(1);
+//! const tally = add(encrypt(pk, yes, random()), encrypt(pk, yes, random()));
+//!
+//! // equals scalarMessage(2)
+//! const result = decrypt(sk, tally);
+//! ```
+//!
+//! Build with e.g. `wasm-pack build --features wasm`.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use wasm_bindgen::prelude::*;
+
+use super::{bsgs::encode_scalar, util, Ciphertext, Error, Fr, G1Affine, PublicKey, SecretKey, G1};
+
+/// The number of random bytes taken by [`keygen`] and [`encrypt`]. They are reduced
+/// modulo the group order in constant time, which is (close to) uniform for 64 bytes.
+pub const RANDOMNESS_SIZE: usize = util::RANDOM_BYTES_SIZE;
+
+/// Create a secret key with the standard G1 generator from [`RANDOMNESS_SIZE`] random
+/// bytes.
+#[wasm_bindgen]
+pub fn keygen(randomness: &[u8]) -> Result<Vec<u8>, JsError> {
+    let x = read_randomness(randomness)?;
+    let sk = SecretKey::from_scalar(x);
+    Ok(encode(&sk)?)
+}
+
+/// Get the public key of the secret key `sk`.
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(sk: &[u8]) -> Result<Vec<u8>, JsError> {
+    let sk: SecretKey = decode(sk)?;
+    Ok(encode(&sk.public_key())?)
+}
+
+/// Get the message encoding the small integer `v`, as in
+/// [`PublicKey::encrypt_scalar`].
+#[wasm_bindgen(js_name = scalarMessage)]
+pub fn scalar_message(v: u32) -> Vec<u8> {
+    write_point(&encode_scalar::<G1>(v.into()))
+}
+
+/// Encrypt the message `msg` with the public key `pk` and [`RANDOMNESS_SIZE`] random
+/// bytes.
+#[wasm_bindgen]
+pub fn encrypt(pk: &[u8], msg: &[u8], randomness: &[u8]) -> Result<Vec<u8>, JsError> {
+    let pk: PublicKey = decode(pk)?;
+    let m = G1Affine::deserialize_compressed(msg).map_err(|_| Error::InvalidEncoding)?;
+    let r = read_randomness(randomness)?;
+    Ok(encode(&pk.encrypt(m, r))?)
+}
+
+/// Decrypt the ciphertext `ct` with the secret key `sk` to get the message.
+#[wasm_bindgen]
+pub fn decrypt(sk: &[u8], ct: &[u8]) -> Result<Vec<u8>, JsError> {
+    let sk: SecretKey = decode(sk)?;
+    let ct: Ciphertext<G1> = decode(ct)?;
+    Ok(write_point(&sk.decrypt(ct)))
+}
+
+/// Add two ciphertexts homomorphically.
+#[wasm_bindgen]
+pub fn add(ct1: &[u8], ct2: &[u8]) -> Result<Vec<u8>, JsError> {
+    let ct1: Ciphertext<G1> = decode(ct1)?;
+    let ct2: Ciphertext<G1> = decode(ct2)?;
+    Ok(encode(&(ct1 + ct2))?)
+}
+
+/// Reduce the secret random bytes in constant time, since they become a secret key or
+/// the randomness of an encryption.
+fn read_randomness(bytes: &[u8]) -> Result<Fr, Error> {
+    util::fr_from_random_bytes_ct(bytes)
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, Error> {
+    bincode::serialize(value).map_err(|_| Error::InvalidEncoding)
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    bincode::deserialize(bytes).map_err(|_| Error::InvalidEncoding)
+}
+
+fn write_point(p: &G1Affine) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    p.serialize_compressed(&mut bytes)
+        .expect("serializing to a vector cannot fail");
+    bytes
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::UniformRand;
+use bls_elgamal::{Ciphertext, Fr, G1Affine, PublicKey, SecretKey, G1};
+use rand::RngCore;
+
+#[test]
+fn test_arbitrary_keys_and_ciphertexts() {
+    let rng = &mut rand::thread_rng();
+    for _ in 0..20 {
+        let mut data = vec![0u8; 512];
+        rng.fill_bytes(&mut data);
+        let u = &mut Unstructured::new(&data);
+
+        let sk = SecretKey::arbitrary(u).unwrap();
+        let pk = PublicKey::arbitrary(u).unwrap();
+        let ct = Ciphertext::<G1>::arbitrary(u).unwrap();
+
+        // generated points are in the prime-order subgroup
+        assert!(ct
+            .0
+            .into_affine()
+            .is_in_correct_subgroup_assuming_on_curve());
+        assert!(ct
+            .1
+            .into_affine()
+            .is_in_correct_subgroup_assuming_on_curve());
+
+        // generated keys are usable and survive serialization
+        let m = G1Affine::rand(rng);
+        let ct = sk.public_key().encrypt(m, Fr::rand(rng));
+        assert_eq!(sk.decrypt(ct), m);
+
+        let deserialized_pk: PublicKey =
+            bincode::deserialize(&bincode::serialize(&pk).unwrap()).unwrap();
+        assert!(pk == deserialized_pk);
+    }
+}
+
+#[test]
+fn test_arbitrary_from_empty_input() {
+    // `Unstructured` pads exhausted input with zeros, which must still yield valid keys.
+    let u = &mut Unstructured::new(&[]);
+    let sk = SecretKey::arbitrary(u).unwrap();
+    let m = G1Affine::generator();
+    let ct = sk.public_key().encrypt(m, Fr::from(7u64));
+    assert_eq!(sk.decrypt(ct), m);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "bech32")]
+
+use ark_std::UniformRand;
+use bls_elgamal::{Error, Fr, G1Affine, PublicKey, SecretKey};
+
+#[test]
+fn test_bech32_public_key() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+
+    let s = pk.to_string();
+    assert!(s.starts_with("bpk1"));
+    assert!(s.parse::<PublicKey>().unwrap() == pk);
+    // bech32 is case-insensitive
+    assert!(s.to_uppercase().parse::<PublicKey>().unwrap() == pk);
+
+    // a changed character breaks the checksum
+    let mut chars: Vec<char> = s.chars().collect();
+    let i = chars.len() / 2;
+    chars[i] = if chars[i] == 'q' { 'p' } else { 'q' };
+    let corrupted: String = chars.into_iter().collect();
+    assert_eq!(
+        corrupted.parse::<PublicKey>().err(),
+        Some(Error::InvalidEncoding)
+    );
+
+    // another prefix with a valid checksum is rejected
+    let hrp = bech32::Hrp::parse("bsk").unwrap();
+    let other = bech32::encode::<bech32::Bech32>(hrp, &pk.to_bytes()).unwrap();
+    assert_eq!(
+        other.parse::<PublicKey>().err(),
+        Some(Error::InvalidEncoding)
+    );
+    assert_eq!("".parse::<PublicKey>().err(), Some(Error::InvalidEncoding));
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "capi")]
+
+use ark_ec::AffineRepr;
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use bls_elgamal::capi::*;
+use bls_elgamal::{Fr, G1Affine, SecretKey};
+
+#[test]
+fn test_capi_roundtrip() {
+    let rng = &mut rand::thread_rng();
+    let seed = [7u8; BLS_ELGAMAL_SEED_SIZE];
+    let mut sk = [0u8; BLS_ELGAMAL_SECRET_KEY_SIZE];
+    let mut pk = [0u8; BLS_ELGAMAL_PUBLIC_KEY_SIZE];
+    let code = unsafe { bls_elgamal_keygen(seed.as_ptr(), sk.as_mut_ptr(), pk.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_OK);
+
+    // the same seed gives the same key pair
+    let mut sk2 = [0u8; BLS_ELGAMAL_SECRET_KEY_SIZE];
+    let mut pk2 = [0u8; BLS_ELGAMAL_PUBLIC_KEY_SIZE];
+    unsafe { bls_elgamal_keygen(seed.as_ptr(), sk2.as_mut_ptr(), pk2.as_mut_ptr()) };
+    assert_eq!((sk, pk), (sk2, pk2));
+
+    let m = G1Affine::rand(rng);
+    let mut msg = Vec::new();
+    m.serialize_compressed(&mut msg).unwrap();
+    let mut r = Vec::new();
+    Fr::rand(rng).serialize_compressed(&mut r).unwrap();
+
+    let mut ct = [0u8; BLS_ELGAMAL_CIPHERTEXT_SIZE];
+    let code =
+        unsafe { bls_elgamal_encrypt(pk.as_ptr(), msg.as_ptr(), r.as_ptr(), ct.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_OK);
+
+    let mut out = [0u8; BLS_ELGAMAL_MESSAGE_SIZE];
+    let code = unsafe { bls_elgamal_decrypt(sk.as_ptr(), ct.as_ptr(), out.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_OK);
+    assert_eq!(G1Affine::deserialize_compressed(&out[..]).unwrap(), m);
+}
+
+#[test]
+fn test_capi_errors() {
+    let mut out = [0u8; BLS_ELGAMAL_MESSAGE_SIZE];
+    let sk = [0xffu8; BLS_ELGAMAL_SECRET_KEY_SIZE];
+    let ct = [0xffu8; BLS_ELGAMAL_CIPHERTEXT_SIZE];
+
+    let code = unsafe { bls_elgamal_decrypt(std::ptr::null(), ct.as_ptr(), out.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_ERR_NULL_POINTER);
+
+    // invalid bytes are reported and the output is untouched
+    let code = unsafe { bls_elgamal_decrypt(sk.as_ptr(), ct.as_ptr(), out.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_ERR_INVALID_ENCODING);
+    assert_eq!(out, [0u8; BLS_ELGAMAL_MESSAGE_SIZE]);
+}
+
+#[test]
+fn test_capi_corrupt_key() {
+    let mut sk = [0u8; BLS_ELGAMAL_SECRET_KEY_SIZE];
+    let mut pk = [0u8; BLS_ELGAMAL_PUBLIC_KEY_SIZE];
+    unsafe {
+        bls_elgamal_keygen(
+            [1u8; BLS_ELGAMAL_SEED_SIZE].as_ptr(),
+            sk.as_mut_ptr(),
+            pk.as_mut_ptr(),
+        )
+    };
+    let mut other_sk = [0u8; BLS_ELGAMAL_SECRET_KEY_SIZE];
+    let mut other_pk = [0u8; BLS_ELGAMAL_PUBLIC_KEY_SIZE];
+    unsafe {
+        bls_elgamal_keygen(
+            [2u8; BLS_ELGAMAL_SEED_SIZE].as_ptr(),
+            other_sk.as_mut_ptr(),
+            other_pk.as_mut_ptr(),
+        )
+    };
+
+    // the secret of one key with the public key of another
+    sk[BLS_ELGAMAL_SCALAR_SIZE..].copy_from_slice(&other_pk);
+    let ct = {
+        let mut ct = [0u8; BLS_ELGAMAL_CIPHERTEXT_SIZE];
+        let mut msg = Vec::new();
+        G1Affine::rand(&mut rand::thread_rng())
+            .serialize_compressed(&mut msg)
+            .unwrap();
+        let mut r = Vec::new();
+        Fr::from(5u64).serialize_compressed(&mut r).unwrap();
+        unsafe { bls_elgamal_encrypt(pk.as_ptr(), msg.as_ptr(), r.as_ptr(), ct.as_mut_ptr()) };
+        ct
+    };
+    let mut out = [0u8; BLS_ELGAMAL_MESSAGE_SIZE];
+    let code = unsafe { bls_elgamal_decrypt(sk.as_ptr(), ct.as_ptr(), out.as_mut_ptr()) };
+    assert_eq!(code, BLS_ELGAMAL_ERR_CORRUPT_KEY);
+    assert_eq!(out, [0u8; BLS_ELGAMAL_MESSAGE_SIZE]);
+}
+
+#[test]
+fn test_capi_degenerate_key() {
+    let rng = &mut rand::thread_rng();
+    let mut msg = Vec::new();
+    G1Affine::rand(rng).serialize_compressed(&mut msg).unwrap();
+    let mut r = Vec::new();
+    Fr::rand(rng).serialize_compressed(&mut r).unwrap();
+    let encrypt = |pk: &[u8]| {
+        let mut ct = [0u8; BLS_ELGAMAL_CIPHERTEXT_SIZE];
+        let code =
+            unsafe { bls_elgamal_encrypt(pk.as_ptr(), msg.as_ptr(), r.as_ptr(), ct.as_mut_ptr()) };
+        (code, ct)
+    };
+
+    // a key that is the identity would leave the message in the clear
+    let degenerate = SecretKey::new(G1Affine::rand(rng), Fr::zero())
+        .public_key()
+        .to_bytes();
+    assert_eq!(
+        encrypt(&degenerate),
+        (
+            BLS_ELGAMAL_ERR_DEGENERATE_KEY,
+            [0u8; BLS_ELGAMAL_CIPHERTEXT_SIZE]
+        )
+    );
+
+    // so would a key with the identity generator
+    let mut identity = Vec::new();
+    G1Affine::zero()
+        .serialize_compressed(&mut identity)
+        .unwrap();
+    identity.extend_from_slice(&identity.clone());
+    assert_eq!(encrypt(&identity).0, BLS_ELGAMAL_ERR_INVALID_GENERATOR);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "constant_time")]
+
+use ark_ec::AffineRepr;
+use ark_std::UniformRand;
+use bls_elgamal::{Fr, G1Affine, SecretKey};
+
+#[test]
+fn test_decrypt_eq() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    for _ in 0..10 {
+        let m = G1Affine::rand(rng);
+        let ct = pk.encrypt(m, Fr::rand(rng));
+        assert!(bool::from(sk.decrypt_eq(ct, m)));
+        assert!(!bool::from(sk.decrypt_eq(ct, G1Affine::rand(rng))));
+        assert!(!bool::from(sk.decrypt_eq(ct, -m)));
+        assert!(!bool::from(sk.decrypt_eq(ct, G1Affine::zero())));
+    }
+
+    // the identity compares equal only to the identity
+    let ct = pk.encrypt(G1Affine::zero(), Fr::rand(rng));
+    assert!(bool::from(sk.decrypt_eq(ct, G1Affine::zero())));
+    assert!(!bool::from(sk.decrypt_eq(ct, G1Affine::generator())));
+}
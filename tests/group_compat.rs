@@ -0,0 +1,120 @@
+#![cfg(feature = "group-compat")]
+
+use ark_ec::AffineRepr;
+use ark_serialize::CanonicalSerialize;
+use ark_std::UniformRand;
+use bls_elgamal::{
+    group_compat::{Point, Scalar},
+    Fr, G1Affine, SecretKey, G1,
+};
+use ff::{Field, PrimeField};
+use group::Group;
+
+#[test]
+fn test_scalar_constants() {
+    assert_eq!(Scalar::TWO_INV.double(), Scalar::ONE);
+    assert_eq!(
+        Scalar::ROOT_OF_UNITY * Scalar::ROOT_OF_UNITY_INV,
+        Scalar::ONE
+    );
+
+    // ROOT_OF_UNITY = MULTIPLICATIVE_GENERATOR^t and DELTA = MULTIPLICATIVE_GENERATOR^(2^S)
+    let (g, t) = (Scalar::MULTIPLICATIVE_GENERATOR, t_limbs());
+    assert_eq!(g.pow_vartime(t), Scalar::ROOT_OF_UNITY);
+    assert_eq!(g.pow_vartime([1u64 << Scalar::S]), Scalar::DELTA);
+    assert_eq!(
+        Scalar::ROOT_OF_UNITY.pow_vartime([1u64 << Scalar::S]),
+        Scalar::ONE
+    );
+    assert_ne!(
+        Scalar::ROOT_OF_UNITY.pow_vartime([1u64 << (Scalar::S - 1)]),
+        Scalar::ONE
+    );
+
+    // the modulus is zero, and one less is the largest representable scalar
+    let mut modulus = [0u8; 32];
+    hex_to_le(Scalar::MODULUS, &mut modulus);
+    assert!(bool::from(Scalar::from_repr(modulus).is_none()));
+    modulus[0] -= 1;
+    assert_eq!(Scalar::from_repr(modulus).unwrap(), -Scalar::ONE);
+}
+
+#[test]
+fn test_scalar_field() {
+    let rng = &mut rand::thread_rng();
+    let (a, b) = (Scalar::random(&mut *rng), Scalar::random(&mut *rng));
+
+    assert_eq!(Fr::from(a + b), a.0 + b.0);
+    assert_eq!(Fr::from(a * b), a.0 * b.0);
+    assert_eq!(a - a, Scalar::ZERO);
+    assert_eq!(a * a.invert().unwrap(), Scalar::ONE);
+    assert!(bool::from(Scalar::ZERO.invert().is_none()));
+    assert_eq!([a, b].iter().sum::<Scalar>(), a + b);
+    assert_eq!([a, b].into_iter().product::<Scalar>(), a * b);
+
+    let square = a.square();
+    let root = square.sqrt().unwrap();
+    assert!(root == a || root == -a);
+    let (is_square, ratio) = Scalar::sqrt_ratio(&(square * b.square()), &b.square());
+    assert!(bool::from(is_square));
+    assert_eq!(ratio.square(), square);
+
+    // the repr is the compressed arkworks serialization
+    let mut bytes = Vec::new();
+    a.0.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(a.to_repr().as_ref(), &bytes[..]);
+    assert_eq!(Scalar::from_repr(a.to_repr()).unwrap(), a);
+    assert!(bool::from(Scalar::from(3).is_odd()));
+}
+
+#[test]
+fn test_point_group() {
+    let rng = &mut rand::thread_rng();
+    let p = Point::random(&mut *rng);
+    let s = Scalar::random(&mut *rng);
+
+    assert_eq!(G1::from(p * s), p.0 * s.0);
+    assert_eq!(p.double(), p + p);
+    assert_eq!(p - p, Point::identity());
+    assert!(bool::from(Point::identity().is_identity()));
+    assert_eq!(Point::generator().0, G1::from(G1Affine::generator()));
+    assert_eq!([p, p].iter().sum::<Point>(), p.double());
+}
+
+#[test]
+fn test_encrypt_decrypt_group_compat() {
+    // a message and randomness produced by code generic over the `group` traits
+    fn commit<P: Group>(v: P::Scalar, rng: &mut rand::rngs::ThreadRng) -> (P, P::Scalar) {
+        (P::generator() * v, P::Scalar::random(rng))
+    }
+
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let (m, r) = commit::<Point>(Scalar::from(42), rng);
+
+    let ct = sk.public_key().encrypt_projective(m.into(), r.into());
+    assert_eq!(Point::from(G1::from(sk.decrypt(ct))), m);
+}
+
+/// The odd part t of the modulus minus one, where r - 1 = 2^S * t.
+fn t_limbs() -> [u64; 4] {
+    let mut bytes = [0u8; 32];
+    hex_to_le(Scalar::MODULUS, &mut bytes);
+    bytes[0] -= 1;
+    let mut limbs: [u64; 4] =
+        std::array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap()));
+    for _ in 0..Scalar::S {
+        for i in 0..4 {
+            limbs[i] = (limbs[i] >> 1) | limbs.get(i + 1).map_or(0, |l| l << 63);
+        }
+    }
+    limbs
+}
+
+fn hex_to_le(hex: &str, out: &mut [u8; 32]) {
+    let hex = hex.trim_start_matches("0x");
+    for (i, byte) in out.iter_mut().enumerate() {
+        let j = hex.len() - 2 * (i + 1);
+        *byte = u8::from_str_radix(&hex[j..j + 2], 16).unwrap();
+    }
+}
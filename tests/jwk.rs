@@ -0,0 +1,39 @@
+#![cfg(feature = "jwk")]
+
+use ark_std::UniformRand;
+use bls_elgamal::{Error, Fr, G1Affine, PublicKey, SecretKey};
+
+#[test]
+fn test_jwk_public_key() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+
+    let jwk = pk.to_jwk();
+    assert_eq!(jwk["kty"], "OKP");
+    assert_eq!(jwk["crv"], "BLS12381G1");
+    assert!(PublicKey::from_jwk(&jwk).unwrap() == pk);
+    // the members survive a round trip through the JSON text
+    let parsed = serde_json::from_str(&jwk.to_string()).unwrap();
+    assert!(PublicKey::from_jwk(&parsed).unwrap() == pk);
+
+    // mismatched key types and curves, and missing or malformed members, are rejected
+    for (name, value) in [
+        ("kty", "EC"),
+        ("crv", "BLS12381G2"),
+        ("x", "not base64!"),
+        ("g", "AAAA"),
+    ] {
+        let mut other = jwk.clone();
+        other[name] = value.into();
+        assert_eq!(
+            PublicKey::from_jwk(&other).err(),
+            Some(Error::InvalidEncoding)
+        );
+    }
+    let mut other = jwk.clone();
+    other.as_object_mut().unwrap().remove("x");
+    assert_eq!(
+        PublicKey::from_jwk(&other).err(),
+        Some(Error::InvalidEncoding)
+    );
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "metrics")]
+
+use ark_std::UniformRand;
+use bls_elgamal::{
+    metrics::{self, OpCounts},
+    Ciphertext, Fr, G1Affine, SecretKey,
+};
+
+#[test]
+fn test_metrics() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let cts: Vec<_> = (0..10)
+        .map(|_| pk.encrypt(G1Affine::rand(rng), Fr::rand(rng)))
+        .collect();
+
+    metrics::reset();
+    assert_eq!(metrics::snapshot(), OpCounts::default());
+
+    // encryption costs two scalar multiplications, decryption one and a normalization
+    let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let _ = sk.decrypt(ct);
+    let counts = metrics::snapshot();
+    assert_eq!(counts.scalar_muls, 3);
+    assert_eq!(counts.normalizations, 1);
+
+    // summing a slice normalizes once, however many ciphertexts it has
+    metrics::reset();
+    let _ = Ciphertext::sum_slice(&cts);
+    assert_eq!(
+        metrics::snapshot(),
+        OpCounts {
+            normalizations: 1,
+            ..Default::default()
+        }
+    );
+}
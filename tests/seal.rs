@@ -0,0 +1,100 @@
+#![cfg(feature = "seal")]
+
+use ark_std::UniformRand;
+use bls_elgamal::{seal::GroupSealed, Error, Fr, G1Affine, PublicKey, SecretKey};
+
+#[test]
+fn test_seal_to_many() {
+    let rng = &mut rand::thread_rng();
+    let g = G1Affine::rand(rng);
+    let sks = (0..3)
+        .map(|_| SecretKey::new(g, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(|sk| sk.public_key()).collect::<Vec<_>>();
+    let plaintext = b"meet at the usual place";
+
+    let sealed = PublicKey::seal_to_many(&pks, plaintext, rng).unwrap();
+    assert_eq!(sealed.recipients(), 3);
+    assert_eq!(sealed.payload().len(), plaintext.len() + 16);
+    for sk in &sks {
+        assert_eq!(sk.open(&sealed).unwrap(), plaintext);
+    }
+
+    // outsiders cannot open it
+    let outsider = SecretKey::new(g, Fr::rand(rng));
+    assert_eq!(outsider.open(&sealed), Err(Error::AuthenticationFailed));
+
+    // the empty payload and a single recipient
+    let sealed = PublicKey::seal_to_many(&pks[..1], b"", rng).unwrap();
+    assert_eq!(sks[0].open(&sealed).unwrap(), b"");
+    assert_eq!(sks[1].open(&sealed), Err(Error::AuthenticationFailed));
+
+    // recipients must share a generator
+    assert_eq!(
+        PublicKey::seal_to_many(&[], plaintext, rng).err(),
+        Some(Error::EmptyInput)
+    );
+    let other = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    assert_eq!(
+        PublicKey::seal_to_many(&[pks[0], other], plaintext, rng).err(),
+        Some(Error::GeneratorMismatch)
+    );
+}
+
+#[test]
+fn test_group_sealed_bytes() {
+    let rng = &mut rand::thread_rng();
+    let g = G1Affine::rand(rng);
+    let sks = (0..4)
+        .map(|_| SecretKey::new(g, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(|sk| sk.public_key()).collect::<Vec<_>>();
+    let plaintext = b"the group chat moved";
+
+    // every recipient opens the message after it went over the wire
+    let sealed = PublicKey::seal_to_many(&pks, plaintext, rng).unwrap();
+    let bytes = sealed.to_bytes();
+    assert_eq!(bytes.len(), 4 + 5 * 48 + 4 + plaintext.len() + 16);
+    let received = GroupSealed::try_from(bytes.as_slice()).unwrap();
+    assert_eq!(received, sealed);
+    assert_eq!(received.recipients(), 4);
+    for sk in &sks {
+        assert_eq!(sk.open(&received).unwrap(), plaintext);
+    }
+    assert_eq!(GroupSealed::try_from(bytes.clone()).unwrap(), sealed);
+
+    // truncated anywhere, or with trailing bytes
+    for len in [0, 3, 4, 100, 4 + 5 * 48 + 2, bytes.len() - 1] {
+        assert!(matches!(
+            GroupSealed::try_from(&bytes[..len]),
+            Err(Error::InvalidLength { .. })
+        ));
+    }
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        GroupSealed::try_from(trailing.as_slice()),
+        Err(Error::InvalidLength {
+            expected: bytes.len(),
+            got: bytes.len() + 1
+        })
+    );
+
+    // no recipients, a payload without a tag, or an invalid point
+    assert_eq!(
+        GroupSealed::try_from(&[0u8; 4][..]),
+        Err(Error::InvalidEncoding)
+    );
+    let mut untagged = bytes[..4 + 5 * 48].to_vec();
+    untagged.extend_from_slice(&[0u8; 4]);
+    assert_eq!(
+        GroupSealed::try_from(untagged.as_slice()),
+        Err(Error::InvalidEncoding)
+    );
+    let mut invalid = bytes.clone();
+    invalid[4..4 + 48].fill(0xff);
+    assert_eq!(
+        GroupSealed::try_from(invalid.as_slice()),
+        Err(Error::InvalidEncoding)
+    );
+}
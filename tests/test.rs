@@ -1,5 +1,16 @@
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::UniformRand;
-use bls_elgamal::{Ciphertext, Fr, G1Affine, PublicKey, SecretKey, G1};
+use bls_elgamal::{
+    bsgs::{bsgs_max_sum, MAX_GIANT_STEPS},
+    encoding, generators, pairing, relation, util, AggregationTree, BallotTally,
+    BatchDecryptionProof, BsgsTable, Ciphertext, CiphertextRef, DecryptKey, DleqStatement,
+    EncryptedBit, EncryptedVec, EphemeralEncryption, Error, Fr, G1Affine, G2Affine, MatchProof,
+    MultiCiphertext, OneOfManyProof, OrProof, PedersenParams, PublicKey, RandomnessGuard,
+    SameSecretProof, ScalarMul, SecretKey, SerializationMode, SigmaProof, SigmaProver,
+    SoftwareScalarMul, Tally, Uncompressed, ZeroProof, G1, G2,
+};
 
 #[test]
 fn test_encrypt_decrypt() {
@@ -18,6 +29,389 @@ fn test_encrypt_decrypt() {
         let ct = pk.encrypt(m, r);
         let decrypted_m = sk.decrypt(ct);
         assert_eq!(m, decrypted_m);
+        assert_eq!(sk.decrypt_projective(ct), G1::from(m));
+    }
+}
+
+#[test]
+fn test_decrypt_batch() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let ms: Vec<_> = (0..10).map(|_| G1Affine::rand(rng)).collect();
+    let cts: Vec<_> = ms.iter().map(|&m| pk.encrypt(m, Fr::rand(rng))).collect();
+    assert_eq!(sk.decrypt_batch(&cts), ms);
+    assert!(sk.decrypt_batch(&[]).is_empty());
+}
+
+#[test]
+fn test_encrypted_bit() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let table = BsgsTable::new(8, 1 << 10).unwrap();
+
+    let bits = [true, false, true, true, false];
+    let encrypted = bits
+        .iter()
+        .map(|&b| pk.encrypt_bit(b, rng))
+        .collect::<Vec<_>>();
+    for (b, bit) in bits.iter().zip(&encrypted) {
+        assert!(bit.verify(&pk));
+        assert_eq!(sk.decrypt_scalar(*bit.ciphertext(), &table), Ok(*b as u64));
+    }
+
+    // the Hamming weight
+    let weight = encrypted[2..]
+        .iter()
+        .cloned()
+        .fold(encrypted[0].clone() + encrypted[1].clone(), |acc, bit| {
+            acc + bit
+        });
+    assert_eq!(sk.decrypt_scalar(weight, &table), Ok(3));
+
+    // the proof is bound to the key
+    let other = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    assert!(!encrypted[0].verify(&other));
+
+    // a verifier rebuilds the bit from the ciphertext and proof it received
+    let ct_bytes = encrypted[0].ciphertext().to_bytes();
+    let mut proof_bytes = Vec::new();
+    encrypted[0]
+        .proof()
+        .serialize_compressed(&mut proof_bytes)
+        .unwrap();
+    let received = EncryptedBit::new(
+        Ciphertext::try_from(&ct_bytes[..]).unwrap(),
+        OrProof::deserialize_compressed(&proof_bytes[..]).unwrap(),
+    );
+    assert_eq!(received, encrypted[0]);
+    assert!(received.verify(&pk));
+    let swapped = EncryptedBit::new(*encrypted[1].ciphertext(), encrypted[0].proof().clone());
+    assert!(!swapped.verify(&pk));
+}
+
+#[test]
+fn test_encrypt_chunks() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let table = BsgsTable::new(encoding::CHUNK_MAX, 1 << 20).unwrap();
+
+    // lengths around the chunk boundaries
+    for msg in [&b""[..], b"a", b"abc", b"abcd", b"hello, chunked world"] {
+        let cts = pk.encrypt_chunks(msg, rng);
+        assert_eq!(cts.len(), 1 + msg.len().div_ceil(encoding::CHUNK_SIZE));
+        assert_eq!(sk.decrypt_chunks(&cts, &table).unwrap(), msg);
+    }
+
+    // the chunks are homomorphic, e.g. adding 1 to the last byte
+    let mut cts = pk.encrypt_chunks(b"abc", rng);
+    cts[1] = cts[1] + pk.encrypt_scalar(1, Fr::rand(rng));
+    assert_eq!(sk.decrypt_chunks(&cts, &table).unwrap(), b"abd");
+
+    // a truncated sequence, trailing padding and foreign points are rejected
+    let points = encoding::encode_chunks(b"abcd");
+    assert_eq!(
+        encoding::decode_chunks(&points[..2], &table),
+        Err(Error::InvalidEncoding)
+    );
+    let mut padded = encoding::encode_chunks(b"abcd\x00\x01");
+    padded[0] = points[0];
+    assert_eq!(
+        encoding::decode_chunks(&padded, &table),
+        Err(Error::InvalidEncoding)
+    );
+    assert_eq!(
+        encoding::decode_chunks(&[points[0], points[1], G1Affine::rand(rng)], &table),
+        Err(Error::OutOfRange)
+    );
+    assert_eq!(
+        encoding::decode_chunks(&[], &table),
+        Err(Error::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_dummy_ciphertext() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let dummies = (0..4).map(|_| pk.dummy_ciphertext(rng)).collect::<Vec<_>>();
+    let mut guard = RandomnessGuard::new();
+    for (i, ct) in dummies.iter().enumerate() {
+        assert_eq!(guard.check(ct), Ok(()));
+        let m = sk.decrypt(*ct);
+        assert!(!m.is_zero());
+        // dummies behave like real ciphertexts, e.g. when rerandomized
+        assert_eq!(sk.decrypt(pk.rerandomize(*ct, Fr::rand(rng))), m);
+        assert!(dummies[..i].iter().all(|other| sk.decrypt(*other) != m));
+    }
+}
+
+#[test]
+fn test_randomness_guard() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    let (m1, m2, r) = (G1Affine::rand(rng), G1Affine::rand(rng), Fr::rand(rng));
+
+    let mut guard = RandomnessGuard::new();
+    assert!(guard.is_empty());
+    assert_eq!(guard.check(&pk.encrypt(m1, r)), Ok(()));
+    assert_eq!(guard.check(&pk.encrypt(m2, Fr::rand(rng))), Ok(()));
+    // the same randomness for another message is caught, also in another form
+    assert_eq!(
+        guard.check(&pk.encrypt_projective(m2.into(), r)),
+        Err(Error::ReusedRandomness)
+    );
+    assert_eq!(guard.len(), 2);
+
+    // rerandomizing gives a fresh component
+    let ct = pk.rerandomize(pk.encrypt(m1, r), Fr::rand(rng));
+    assert_eq!(guard.check(&ct), Ok(()));
+}
+
+#[test]
+fn test_encrypt_reusing_ephemeral() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let ms = (0..5).map(|_| G1Affine::rand(rng)).collect::<Vec<_>>();
+    let r = Fr::rand(rng);
+
+    let cts = pk.encrypt_reusing_ephemeral(&ms, r);
+    assert_eq!(cts.len(), ms.len());
+    for (ct, m) in cts.iter().zip(&ms) {
+        assert_eq!(*ct, pk.encrypt(*m, r));
+        assert_eq!(sk.decrypt(*ct), *m);
+    }
+    // the caveat: the difference of the messages is public
+    assert_eq!(cts[0].1 - cts[1].1, ms[0] - ms[1]);
+    assert!(pk.encrypt_reusing_ephemeral(&[], r).is_empty());
+}
+
+#[test]
+fn test_relation() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let (m, r) = (G1Affine::rand(rng), Fr::rand(rng));
+
+    let ct = pk.encrypt(m, r);
+    assert_eq!(
+        relation::expected_a(pk.generator(), r),
+        G1Affine::from(ct.0)
+    );
+    assert_eq!(relation::expected_b(pk.y(), r, m), G1Affine::from(ct.1));
+    assert_ne!(
+        relation::expected_b(pk.y(), r + Fr::one(), m),
+        G1Affine::from(ct.1)
+    );
+}
+
+#[test]
+fn test_decrypt_into() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let m = G1Affine::rand(rng);
+    let ct = sk.public_key().encrypt(m, Fr::rand(rng));
+
+    let mut out = [0u8; 64];
+    assert_eq!(sk.decrypt_into(ct, &mut out), Ok(48));
+    assert_eq!(G1Affine::deserialize_compressed(&out[..48]).unwrap(), m);
+    assert_eq!(out[48..], [0u8; 16]);
+
+    let mut short = [0u8; 47];
+    assert_eq!(
+        sk.decrypt_into(ct, &mut short),
+        Err(Error::BufferTooSmall {
+            needed: 48,
+            got: 47
+        })
+    );
+    assert_eq!(short, [0u8; 47]);
+}
+
+#[test]
+fn test_decrypt_try_keys() {
+    let rng = &mut rand::thread_rng();
+    let keys = (0..40)
+        .map(|_| SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let m = G1Affine::rand(rng);
+    let ct = keys[17].public_key().encrypt(m, Fr::rand(rng));
+
+    let ms = SecretKey::decrypt_try_keys(ct, &keys);
+    assert_eq!(ms.len(), keys.len());
+    for (key, dec) in keys.iter().zip(&ms) {
+        assert_eq!(*dec, key.decrypt(ct));
+    }
+    assert_eq!(ms.iter().filter(|dec| **dec == m).count(), 1);
+    assert_eq!(ms[17], m);
+    assert!(SecretKey::decrypt_try_keys(ct, &[]).is_empty());
+}
+
+#[test]
+fn test_degenerate_key() {
+    let rng = &mut rand::thread_rng();
+    let (m, r) = (G1Affine::rand(rng), Fr::rand(rng));
+
+    // the secret zero gives the identity as public key, which encrypts in the clear
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::zero()).public_key();
+    assert!(pk.is_degenerate());
+    assert_eq!(G1Affine::from(pk.encrypt(m, r).1), m);
+    assert_eq!(pk.try_encrypt(m, r), Err(Error::DegenerateKey));
+    let parsed = PublicKey::try_from(&pk.to_bytes()[..]).unwrap();
+    assert_eq!(parsed.try_encrypt(m, r), Err(Error::DegenerateKey));
+
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    assert!(!pk.is_degenerate());
+    assert_eq!(pk.try_encrypt(m, r), Ok(pk.encrypt(m, r)));
+}
+
+#[test]
+fn test_encrypt_ephemeral() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+
+    let sealed = pk.encrypt_ephemeral(m, rng);
+    assert_ne!(sealed, pk.encrypt_ephemeral(m, rng));
+    assert_eq!(sk.decrypt(sealed.into()), m);
+
+    // the ephemeral public key and the payload are the points of the ciphertext
+    let r = Fr::rand(rng);
+    let ct = pk.encrypt(m, r);
+    let sealed = EphemeralEncryption::from(ct);
+    assert_eq!(sealed.ephemeral_pub, pk.generator() * r);
+    assert_eq!(sealed.payload, ct.1);
+    assert_eq!(Ciphertext::from(sealed), ct);
+}
+
+#[test]
+fn test_scalar_mul_backend() {
+    // a mock backend counting its calls and computing in software
+    #[derive(Default)]
+    struct Counting(std::cell::Cell<usize>);
+
+    impl ScalarMul<G1> for Counting {
+        fn mul(&self, base: &G1, scalar: &Fr) -> G1 {
+            self.0.set(self.0.get() + 1);
+            SoftwareScalarMul.mul(base, scalar)
+        }
+    }
+
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+    let r = Fr::rand(rng);
+
+    let backend = Counting::default();
+    let ct = pk.encrypt_with(&backend, m, r);
+    assert_eq!(ct, pk.encrypt(m, r));
+    assert_eq!(backend.0.get(), 2);
+
+    assert_eq!(sk.decrypt_with(&backend, ct), m);
+    assert_eq!(backend.0.get(), 3);
+
+    // every other `_with` method routes its scalar multiplications through the backend
+    let backend = Counting::default();
+    let ct2 = pk.encrypt_projective_with(&backend, m.into_group(), r);
+    assert_eq!(ct2, ct);
+    assert_eq!(backend.0.get(), 2);
+
+    assert_eq!(pk.rerandomize_with(&backend, ct, r), pk.rerandomize(ct, r));
+    assert_eq!(backend.0.get(), 4);
+
+    let sk2 = SecretKey::new(pk.generator(), Fr::rand(rng));
+    let rk = sk.rekey(&sk2).unwrap();
+    let rekeyed = sk2
+        .public_key()
+        .rerandomize_and_rekey_with(&backend, ct, &rk, r);
+    assert_eq!(rekeyed, sk2.public_key().rerandomize_and_rekey(ct, &rk, r));
+    assert_eq!(backend.0.get(), 7);
+
+    assert_eq!(sk.decrypt_projective_with(&backend, ct), m.into_group());
+    assert_eq!(backend.0.get(), 8);
+
+    assert_eq!(sk.decrypt_batch_with(&backend, &[ct, ct]), vec![m, m]);
+    assert_eq!(backend.0.get(), 10);
+
+    let table = BsgsTable::<G1>::new(100, 1 << 16).unwrap();
+    let ct = pk.encrypt_scalar(42, r);
+    assert_eq!(sk.decrypt_scalar_with(&backend, ct, &table), Ok(42));
+    assert_eq!(backend.0.get(), 11);
+    assert_eq!(
+        sk.decrypt_value_with(&backend, ct, &table).map(|(_, v)| v),
+        Ok(42)
+    );
+    assert_eq!(backend.0.get(), 12);
+    let ct = pk.encrypt_signed(-42, r);
+    assert_eq!(sk.decrypt_signed_with(&backend, ct, &table), Ok(-42));
+    assert_eq!(backend.0.get(), 13);
+}
+
+#[test]
+fn test_interactive_sigma() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+    let statement = pk.decrypt_statement(&ct, m);
+
+    // the challenge comes from the verifier after the commitment
+    let mut prover = sk.decrypt_prover(&ct);
+    assert_eq!(prover.statement(), &statement);
+    let commitment = prover.commit(rng);
+    let challenge = Fr::rand(rng);
+    let response = prover.respond(challenge).unwrap();
+    assert!(statement.verify(&commitment, challenge, &response));
+    assert!(!statement.verify(&commitment, challenge + Fr::one(), &response));
+    assert!(!pk.decrypt_statement(&ct, G1Affine::rand(rng)).verify(
+        &commitment,
+        challenge,
+        &response
+    ));
+
+    // a prover responds only after committing
+    assert!(sk.decrypt_prover(&ct).respond(challenge).is_none());
+
+    // a generic statement, and a prover with the wrong secret
+    let (g1, g2, x) = (G1::rand(rng), G1::rand(rng), Fr::rand(rng));
+    let statement = DleqStatement::new(g1, g1 * x, g2, g2 * x);
+    for (secret, valid) in [(x, true), (x + Fr::one(), false)] {
+        let mut prover = SigmaProver::new(statement, secret);
+        let commitment = prover.commit(rng);
+        let response = prover.respond(challenge).unwrap();
+        assert_eq!(statement.verify(&commitment, challenge, &response), valid);
+    }
+}
+
+#[test]
+fn test_prepared_decrypt() {
+    let rng = &mut rand::thread_rng();
+    let secrets = [
+        Fr::zero(),
+        Fr::one(),
+        -Fr::one(),
+        Fr::rand(rng),
+        Fr::rand(rng),
+    ];
+    for x in secrets {
+        let sk = SecretKey::new(G1Affine::rand(rng), x);
+        let pk = sk.public_key();
+        let prepared = sk.prepare();
+        for _ in 0..20 {
+            let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+            assert_eq!(prepared.decrypt(ct), sk.decrypt(ct));
+        }
+        let m = G1Affine::rand(rng);
+        assert_eq!(prepared.decrypt(pk.encrypt(m, Fr::zero())), m);
     }
 }
 
@@ -68,6 +462,7 @@ fn test_decrypt_modified_ciphertext() {
 }
 
 #[test]
+#[allow(clippy::op_ref)]
 fn test_homomorphic_ciphertext() {
     let rng = &mut rand::thread_rng();
     for _ in 0..100 {
@@ -91,9 +486,64 @@ fn test_homomorphic_ciphertext() {
         let decrypted_m1 = sk.decrypt(ct3);
         let decrypted_m2 = m1 + m2;
         assert_eq!(decrypted_m1, decrypted_m2);
+
+        // negate a ciphertext, in place or not
+        let mut ct4 = ct3;
+        ct4.negate();
+        assert_eq!(ct4, -ct3);
+        assert_eq!(ct4, -&ct3);
+        assert_eq!(sk.decrypt(ct4), -decrypted_m2);
+        assert_eq!(sk.decrypt(ct4 + ct1), -m2);
+
+        // a map commuting with scalar multiplication keeps the ciphertext decryptable
+        let k = Fr::rand(rng);
+        let ct5 = ct1.map_points(|p| p * k);
+        assert_eq!(ct5, ct1 * k);
+        assert_eq!(sk.decrypt(ct5), m1 * k);
+
+        // scalar multiplication commutes in notation
+        assert_eq!(k * ct1, ct1 * k);
+        assert_eq!(k * &ct1, &ct1 * k);
+
+        // replacing b with b + (m2 - m1) keeps a and changes the message to m2
+        let ct6 = ct1.replace_b((ct1.1 + m2 - m1).into());
+        assert_eq!(ct6.0, ct1.0);
+        assert_eq!(sk.decrypt(ct6), m2);
     }
 }
 
+#[test]
+fn test_ciphertext_ref() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let ms = (0..10).map(|_| G1Affine::rand(rng)).collect::<Vec<_>>();
+    let cts = ms
+        .iter()
+        .map(|&m| pk.encrypt(m, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+
+    let sum: Ciphertext<G1> = cts.iter().map(Ciphertext::view).sum();
+    assert_eq!(sk.decrypt(sum), ms.iter().copied().sum::<G1>());
+    assert_eq!(cts.iter().copied().sum::<Ciphertext<G1>>(), sum);
+
+    // the same sum, normalized
+    let normalized = Ciphertext::sum_slice(&cts);
+    assert_eq!(normalized, sum);
+    assert!(normalized.0.z.is_one() && normalized.1.z.is_one());
+    assert!(Ciphertext::<G1>::sum_slice(&[]).is_degenerate());
+
+    let mut acc = cts[0];
+    acc += cts[1].view();
+    acc -= cts[2].view();
+    assert_eq!(acc, cts[0] + cts[1] - cts[2]);
+    assert_eq!(cts[0].view() + cts[1].view(), cts[0] + cts[1]);
+    assert_eq!(cts[0].view() - cts[1].view(), cts[0] - cts[1]);
+    assert_eq!(cts[0] + CiphertextRef::from(&cts[1]), cts[0] + cts[1]);
+    assert_eq!(cts[0].view().to_ciphertext(), cts[0]);
+}
+
 #[test]
 fn test_serde() {
     let rng = &mut rand::thread_rng();
@@ -128,3 +578,1351 @@ fn test_serde() {
     let decrypt_m = deserialized_sk.decrypt(check_ct);
     assert_eq!(m, decrypt_m);
 }
+
+#[test]
+fn test_serde_versions() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    // a bincode blob is the length (8 bytes), the curve id (4 bytes), the version and
+    // the payload
+    let pk_blob = bincode::serialize(&pk).unwrap();
+    assert_eq!(pk_blob[12], 1);
+    let curve_id = &pk_blob[8..12];
+
+    // version 1 secret keys (written by default) are the secret followed by the encoded
+    // public key
+    let v1 = [curve_id, &[1], &sk.secret_bytes(), &pk_blob].concat();
+    let v1_blob = bincode::serialize(&v1).unwrap();
+    assert_eq!(bincode::serialize(&sk).unwrap(), v1_blob);
+    let loaded = bincode::deserialize::<SecretKey>(&v1_blob).unwrap();
+    assert!(loaded == sk && loaded.validate());
+
+    // the embedded public key of version 1 is checked against the secret
+    let other_pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    let corrupt = [
+        curve_id,
+        &[1],
+        &sk.secret_bytes(),
+        &bincode::serialize(&other_pk).unwrap(),
+    ]
+    .concat();
+    let err = bincode::deserialize::<SecretKey>(&bincode::serialize(&corrupt).unwrap())
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), Error::CorruptKey.to_string());
+
+    // version 2 is the generator followed by the secret, as in `to_bytes_compact`
+    let v2 = [curve_id, &[2], &sk.to_bytes_compact()].concat();
+    let v2_blob = bincode::serialize(&v2).unwrap();
+    assert_eq!(v2_blob.len(), 8 + 4 + 1 + 48 + 32);
+    assert!(v2_blob.len() < v1_blob.len());
+    assert!(bincode::deserialize::<SecretKey>(&v2_blob).unwrap() == sk);
+
+    // version 2 rejects trailing bytes and the identity generator
+    let trailing = bincode::serialize(&[v2.as_slice(), &[0]].concat()).unwrap();
+    let err = bincode::deserialize::<SecretKey>(&trailing).err().unwrap();
+    assert_eq!(err.to_string(), Error::InvalidEncoding.to_string());
+    let mut identity = Vec::new();
+    G1Affine::zero()
+        .serialize_compressed(&mut identity)
+        .unwrap();
+    let identity = [curve_id, &[2], &identity, &sk.secret_bytes()].concat();
+    let err = bincode::deserialize::<SecretKey>(&bincode::serialize(&identity).unwrap())
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), Error::InvalidGenerator.to_string());
+
+    // unknown versions are rejected
+    let unsupported = Error::UnsupportedVersion(3).to_string();
+    for mut blob in [pk_blob, v2_blob] {
+        blob[12] = 3;
+        let err = bincode::deserialize::<SecretKey>(&blob).err().unwrap();
+        assert_eq!(err.to_string(), unsupported);
+        let err = bincode::deserialize::<PublicKey>(&blob).err().unwrap();
+        assert_eq!(err.to_string(), unsupported);
+    }
+}
+
+#[test]
+fn test_serde_uncompressed() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+
+    // every point doubles in size, and either form deserializes
+    let compressed = bincode::serialize(&ct).unwrap();
+    let uncompressed = bincode::serialize(&Uncompressed(ct)).unwrap();
+    assert_eq!(uncompressed.len(), compressed.len() + 2 * 48);
+    assert_eq!(
+        bincode::deserialize::<Ciphertext<G1>>(&uncompressed).unwrap(),
+        ct
+    );
+    assert_eq!(
+        bincode::deserialize::<Uncompressed<Ciphertext<G1>>>(&compressed).unwrap(),
+        Uncompressed(ct)
+    );
+
+    let compressed = bincode::serialize(&pk).unwrap();
+    let uncompressed = bincode::serialize(&Uncompressed(pk)).unwrap();
+    assert_eq!(uncompressed.len(), compressed.len() + 2 * 48);
+    assert!(bincode::deserialize::<PublicKey>(&uncompressed).unwrap() == pk);
+
+    let compressed = bincode::serialize(&sk).unwrap();
+    let uncompressed = bincode::serialize(&Uncompressed(sk)).unwrap();
+    assert_eq!(uncompressed.len(), compressed.len() + 2 * 48);
+    assert!(bincode::deserialize::<SecretKey>(&uncompressed).unwrap() == sk);
+
+    assert_eq!(SerializationMode::default(), SerializationMode::Compressed);
+}
+
+#[test]
+fn test_encrypt_decrypt_scalar() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    let table = BsgsTable::new(1000, 1 << 20).unwrap();
+    for v in [0, 1, 31, 32, 777, 1000] {
+        let ct = pk.encrypt_scalar(v, Fr::rand(rng));
+        assert_eq!(sk.decrypt_scalar(ct, &table), Ok(v));
+    }
+
+    // values beyond the maximum of the table are reported rather than searched forever
+    let ct = pk.encrypt_scalar(1001, Fr::rand(rng));
+    assert_eq!(sk.decrypt_scalar(ct, &table), Err(Error::OutOfRange));
+    let ct = pk.encrypt_scalar(1 << 20, Fr::rand(rng));
+    assert_eq!(sk.decrypt_scalar(ct, &table), Err(Error::OutOfRange));
+
+    // or rejected at encryption time with the bounded variant
+    let ct = pk.encrypt_scalar_bounded(1000, table.max(), Fr::rand(rng));
+    assert_eq!(sk.decrypt_scalar(ct.unwrap(), &table), Ok(1000));
+    assert_eq!(
+        pk.encrypt_scalar_bounded(1001, table.max(), Fr::rand(rng)),
+        Err(Error::OutOfRange)
+    );
+}
+
+#[test]
+fn test_bsgs_table_memory_budget() {
+    // the balanced split fits in a large budget
+    let table = BsgsTable::<G1>::new(9999, 1 << 20).unwrap();
+    assert_eq!(table.baby_steps(), 100);
+    assert_eq!(table.giant_steps(), 100);
+
+    // a small budget shrinks the baby steps and increases the giant steps instead
+    let table = BsgsTable::<G1>::new(1 << 32, 1 << 16).unwrap();
+    assert!(table.baby_steps() < 1 << 16);
+    assert!(table.baby_steps() * table.giant_steps() > 1 << 32);
+    assert!(table.giant_steps() <= MAX_GIANT_STEPS);
+
+    // lookups find values in any batch of giant steps, up to the last one
+    let table = BsgsTable::<G1>::new(9999, 1 << 10).unwrap();
+    assert!(table.giant_steps() > 1000);
+    for v in [0, 1, 2000, 5555, 9998, 9999] {
+        assert_eq!(
+            table.discrete_log(G1Affine::generator() * Fr::from(v)),
+            Ok(v)
+        );
+    }
+    assert_eq!(
+        table.discrete_log(G1Affine::generator() * Fr::from(10000u64)),
+        Err(Error::OutOfRange)
+    );
+
+    // infeasible ranges are rejected
+    assert!(matches!(
+        BsgsTable::<G1>::new(1 << 40, 1 << 16),
+        Err(Error::TableTooLarge)
+    ));
+    assert!(matches!(
+        BsgsTable::<G1>::new(u64::MAX, 1 << 16),
+        Err(Error::TableTooLarge)
+    ));
+    assert!(matches!(
+        BsgsTable::<G1>::new(1000, 0),
+        Err(Error::TableTooLarge)
+    ));
+}
+
+#[test]
+fn test_tally() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    let mut tally = Tally::new();
+    let mut total = 0;
+    for i in 0..10_000u64 {
+        let v = i % 3;
+        total += v;
+        // small randomness keeps the test fast in debug builds
+        let r = Fr::from(rand::Rng::gen::<u32>(rng));
+        tally.add(&pk.encrypt_scalar(v, r));
+    }
+    tally.add_plaintext(G1Affine::generator());
+    total += 1;
+    assert_eq!(tally.count(), 10_000);
+
+    let table = BsgsTable::new(total, 1 << 20).unwrap();
+    assert_eq!(sk.decrypt_scalar(tally.finalize(), &table), Ok(total));
+
+    // the table covers the sum of 10000 values up to 2, but not up to 3
+    let table = BsgsTable::new(bsgs_max_sum(2, 10_000), 1 << 20).unwrap();
+    assert_eq!(tally.check_range(2, &table), Ok(()));
+    assert_eq!(tally.check_range(3, &table), Err(Error::OutOfRange));
+    assert_eq!(bsgs_max_sum(u64::MAX, 2), u64::MAX);
+
+    // collecting ciphertexts gives the same tally
+    let cts = (1..=10u64)
+        .map(|v| pk.encrypt_scalar(v, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let borrowed: Tally<G1> = cts.iter().collect();
+    let collected: Tally<G1> = cts.into_iter().collect();
+    assert_eq!(collected, borrowed);
+    assert_eq!(collected.count(), 10);
+    assert_eq!(sk.decrypt_scalar(collected.finalize(), &table), Ok(55));
+}
+
+#[test]
+fn test_ballot_tally() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    // 3 candidates, each ballot votes for exactly one of them
+    let mut tally = BallotTally::new(3);
+    let mut totals = [0u64; 3];
+    for i in 0..20 {
+        let choice = i % 3;
+        totals[choice] += 1;
+        let ballot: Vec<_> = (0..3)
+            .map(|c| pk.encrypt_scalar((c == choice) as u64, Fr::rand(rng)))
+            .collect();
+        tally.add_ballot(&ballot).unwrap();
+    }
+    assert_eq!(tally.candidates(), 3);
+    assert_eq!(tally.count(), 20);
+
+    // a ballot with the wrong number of ciphertexts is rejected and not counted
+    let short = [pk.encrypt_scalar(1, Fr::rand(rng))];
+    assert_eq!(tally.add_ballot(&short), Err(Error::LengthMismatch));
+    assert_eq!(tally.count(), 20);
+
+    let table = BsgsTable::new(20, 1 << 20).unwrap();
+    let results: Vec<u64> = tally
+        .finalize()
+        .into_iter()
+        .map(|ct| sk.decrypt_scalar(ct, &table).unwrap())
+        .collect();
+    assert_eq!(results, totals);
+}
+
+#[test]
+fn test_aggregation_tree() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let table = BsgsTable::new(100, 1 << 20).unwrap();
+
+    // an odd number of inputs, so some nodes are promoted
+    let inputs: Vec<_> = (0..7u64)
+        .map(|v| pk.encrypt_scalar(v, Fr::rand(rng)))
+        .collect();
+    let tree = AggregationTree::new(&inputs).unwrap();
+    let (root, sum) = (tree.root(), tree.sum());
+    assert_eq!(sum, Ciphertext::sum_slice(&inputs));
+    assert_eq!(sk.decrypt_scalar(sum, &table), Ok(21));
+
+    for (i, input) in inputs.iter().enumerate() {
+        let proof = tree.prove(i).unwrap();
+        assert_eq!(proof.index(), i);
+        assert!(proof.verify(&root, &sum, input));
+        // another input, another sum or another root fails
+        assert!(!proof.verify(&root, &sum, &inputs[(i + 1) % inputs.len()]));
+        assert!(!proof.verify(&root, &(sum + inputs[0]), input));
+        assert!(!proof.verify(&[0; 32], &sum, input));
+    }
+    assert!(tree.prove(inputs.len()).is_none());
+
+    // a single input is its own root
+    let tree = AggregationTree::new(&inputs[..1]).unwrap();
+    assert!(tree
+        .prove(0)
+        .unwrap()
+        .verify(&tree.root(), &inputs[0], &inputs[0]));
+    assert_eq!(
+        AggregationTree::<G1>::new(&[]).err(),
+        Some(Error::EmptyInput)
+    );
+}
+
+#[test]
+fn test_encrypt_projective() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    let m1 = G1Affine::rand(rng);
+    let m2 = G1Affine::rand(rng);
+    let r = Fr::rand(rng);
+
+    // a projective message gives the same ciphertext as its affine form
+    let m = m1 + m2;
+    let ct = pk.encrypt_projective(m, r);
+    assert_eq!(ct, pk.encrypt(m.into(), r));
+    assert_eq!(sk.decrypt(ct), G1Affine::from(m));
+}
+
+#[test]
+fn test_pairing_verify_ciphertext() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    let h = G2Affine::rand(rng);
+    let y2 = sk.public_key_g2(h);
+    assert!(pairing::verify_public_key_g2(&pk, h, y2));
+
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+    assert!(pairing::verify_ciphertext(h, y2, ct, m));
+
+    // a tampered message is rejected
+    assert!(!pairing::verify_ciphertext(h, y2, ct, G1Affine::rand(rng)));
+
+    // a G2 key of another secret is rejected
+    let other = SecretKey::new(g1, Fr::rand(rng)).public_key_g2(h);
+    assert!(!pairing::verify_public_key_g2(&pk, h, other));
+    assert!(!pairing::verify_ciphertext(h, other, ct, m));
+
+    // the prepared verifier agrees with the one-off check
+    let verifier = pairing::PreparedVerifier::new(h, y2);
+    assert!(verifier.verify(ct, m));
+    assert!(!verifier.verify(ct, G1Affine::rand(rng)));
+    assert!(!pairing::PreparedVerifier::new(h, other).verify(ct, m));
+}
+
+#[test]
+fn test_util_fr_conversions() {
+    let v: Fr = util::fr_from_u64(42);
+    assert_eq!(v, Fr::from(42u64));
+    assert_eq!(util::fr_to_u64_checked(&v), Some(42));
+    assert_eq!(
+        util::fr_to_u64_checked(&util::fr_from_u64::<Fr>(u64::MAX)),
+        Some(u64::MAX)
+    );
+
+    // negative values are field negations
+    let v: Fr = util::fr_from_i64(-42);
+    assert_eq!(v, -Fr::from(42u64));
+    assert_eq!(util::fr_to_u64_checked(&v), None);
+    assert_eq!(util::fr_from_i64::<Fr>(i64::MIN), -Fr::from(1u64 << 63));
+    assert_eq!(util::fr_from_i64::<Fr>(7), Fr::from(7u64));
+
+    // batch inversion agrees with inverting one by one, and skips zeros
+    let rng = &mut rand::thread_rng();
+    let scalars = [Fr::rand(rng), Fr::from(0u64), Fr::rand(rng)];
+    let mut inverses = scalars;
+    util::batch_invert(&mut inverses);
+    assert_eq!(inverses[0], scalars[0].inverse().unwrap());
+    assert_eq!(inverses[1], Fr::from(0u64));
+    assert_eq!(inverses[2], scalars[2].inverse().unwrap());
+
+    // large elements are not small integers
+    assert_eq!(
+        util::fr_to_u64_checked(&Fr::rand(&mut rand::thread_rng())),
+        None
+    );
+    assert_eq!(
+        util::fr_to_u64_checked(&(Fr::from(u64::MAX) + Fr::from(1u64))),
+        None
+    );
+}
+
+#[test]
+fn test_rerandomize_and_rekey() {
+    let rng = &mut rand::thread_rng();
+    let g1 = G1Affine::rand(rng);
+
+    // a chain of mix-net nodes, each re-encrypting to the next one
+    let nodes: Vec<_> = (0..4).map(|_| SecretKey::new(g1, Fr::rand(rng))).collect();
+
+    let m = G1Affine::rand(rng);
+    let mut ct = nodes[0].public_key().encrypt(m, Fr::rand(rng));
+    for pair in nodes.windows(2) {
+        let rk = pair[0].rekey(&pair[1]).unwrap();
+        let new_ct = pair[1]
+            .public_key()
+            .rerandomize_and_rekey(ct, &rk, Fr::rand(rng));
+        assert_ne!(ct.0, new_ct.0);
+
+        // the fused transform decrypts like the two separate steps
+        assert_eq!(pair[1].decrypt(new_ct), pair[1].decrypt(rk.reencrypt(ct)));
+        ct = new_ct;
+    }
+    assert_eq!(nodes[3].decrypt(ct), m);
+
+    // keys with different generators cannot be linked
+    let other = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    assert_eq!(nodes[0].rekey(&other), Err(Error::GeneratorMismatch));
+}
+
+#[test]
+fn test_decrypt_difference() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let g1 = G1Affine::rand(rng);
+
+    let sk = SecretKey::new(g1, x);
+    let pk = sk.public_key();
+
+    let m1 = G1Affine::rand(rng);
+    let m2 = G1Affine::rand(rng);
+    let ct1 = pk.encrypt(m1, Fr::rand(rng));
+    let ct2 = pk.encrypt(m2, Fr::rand(rng));
+
+    assert_eq!(sk.decrypt(ct1 - ct2), G1Affine::from(m1 - m2));
+    assert_eq!(sk.decrypt_difference(ct1, ct2), G1Affine::from(m1 - m2));
+    assert_eq!(sk.decrypt_difference(ct1, ct1), G1Affine::zero());
+}
+
+#[test]
+fn test_try_new_rejects_identity_generator() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+
+    assert!(matches!(
+        SecretKey::try_new(G1Affine::zero(), x),
+        Err(Error::InvalidGenerator)
+    ));
+
+    let sk = SecretKey::try_new(G1Affine::rand(rng), x).unwrap();
+    let m = G1Affine::rand(rng);
+    let ct = sk.public_key().encrypt(m, Fr::rand(rng));
+    assert_eq!(sk.decrypt(ct), m);
+}
+
+#[test]
+fn test_ciphertext_array() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let ct = sk.public_key().encrypt(G1Affine::rand(rng), Fr::rand(rng));
+
+    let [a, b] = ct.as_array();
+    assert_eq!((a, b), (ct.0, ct.1));
+    assert_eq!(Ciphertext::from_array(ct.as_array()), ct);
+}
+
+#[test]
+fn test_decrypt_value() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let table = BsgsTable::new(1000, 1 << 20).unwrap();
+    let ct = pk.encrypt_scalar(777, Fr::rand(rng));
+    assert_eq!(sk.decrypt_value(ct, &table), Ok((sk.decrypt(ct), 777)));
+
+    let ct = pk.encrypt_scalar(1001, Fr::rand(rng));
+    assert_eq!(sk.decrypt_value(ct, &table), Err(Error::OutOfRange));
+}
+
+#[test]
+fn test_secret_key_bytes_compact() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+
+    let bytes = sk.to_bytes_compact();
+    assert_eq!(bytes.len(), 48 + 32);
+    assert!(bytes.len() < bincode::serialize(&sk).unwrap().len());
+    assert!(SecretKey::from_bytes_compact(&bytes).unwrap() == sk);
+
+    assert_eq!(
+        SecretKey::from_bytes_compact(&bytes[..bytes.len() - 1]).err(),
+        Some(Error::InvalidEncoding)
+    );
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert_eq!(
+        SecretKey::from_bytes_compact(&longer).err(),
+        Some(Error::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_secret_bytes() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let sk = SecretKey::from_scalar(x);
+
+    let bytes = sk.secret_bytes();
+    assert_eq!(Fr::from_le_bytes_mod_order(&bytes), x);
+    assert!(SecretKey::from_secret_bytes(&bytes).unwrap() == sk);
+
+    // the modulus is not reduced
+    let mut modulus = [0u8; 32];
+    modulus.copy_from_slice(&Fr::MODULUS.to_bytes_le());
+    assert_eq!(
+        SecretKey::from_secret_bytes(&modulus).err(),
+        Some(Error::InvalidEncoding)
+    );
+    assert_eq!(
+        SecretKey::from_secret_bytes(&[0xff; 32]).err(),
+        Some(Error::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_or_proof() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let candidates = [G1Affine::zero(), G1Affine::generator()];
+
+    for actual in [false, true] {
+        let r = Fr::rand(rng);
+        let ct = pk.encrypt(candidates[actual as usize], r);
+        let proof = pk.prove_encryption_of_one_of(&ct, r, actual, candidates, rng);
+        assert!(pk.verify_encryption_of_one_of(&ct, candidates, &proof));
+
+        // the proof is bound to the ciphertext and the candidates
+        let other = pk.rerandomize(ct, Fr::rand(rng));
+        assert!(!pk.verify_encryption_of_one_of(&other, candidates, &proof));
+        let swapped = [candidates[0], G1Affine::rand(rng)];
+        assert!(!pk.verify_encryption_of_one_of(&ct, swapped, &proof));
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        let decoded = OrProof::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(pk.verify_encryption_of_one_of(&ct, candidates, &decoded));
+    }
+
+    // a ciphertext of another message cannot be proven
+    let r = Fr::rand(rng);
+    let ct = pk.encrypt(G1Affine::rand(rng), r);
+    for actual in [false, true] {
+        let proof = pk.prove_encryption_of_one_of(&ct, r, actual, candidates, rng);
+        assert!(!pk.verify_encryption_of_one_of(&ct, candidates, &proof));
+    }
+}
+
+#[test]
+fn test_one_of_many_proof() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let candidates = (0..4)
+        .map(|v| pk.encrypt_scalar(v, Fr::from(0u64)).1.into())
+        .collect::<Vec<G1Affine>>();
+
+    for (index, &m) in candidates.iter().enumerate() {
+        let r = Fr::rand(rng);
+        let ct = pk.encrypt(m, r);
+        let proof = pk.prove_encryption_of_one_of_many(&ct, r, index, &candidates, rng);
+        assert!(pk.verify_encryption_of_one_of_many(&ct, &candidates, &proof));
+
+        // the challenge binds every candidate in order
+        let mut reordered = candidates.clone();
+        reordered.swap(index, (index + 1) % candidates.len());
+        assert!(!pk.verify_encryption_of_one_of_many(&ct, &reordered, &proof));
+        assert!(!pk.verify_encryption_of_one_of_many(&ct, &candidates[..3], &proof));
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 2 * (8 + 32 * candidates.len()));
+        let decoded = OneOfManyProof::deserialize_compressed(&bytes[..]).unwrap();
+        assert!(pk.verify_encryption_of_one_of_many(&ct, &candidates, &decoded));
+    }
+
+    // a plaintext outside of the candidates cannot be proven
+    let r = Fr::rand(rng);
+    let ct = pk.encrypt_scalar(4, r);
+    for index in 0..candidates.len() {
+        let proof = pk.prove_encryption_of_one_of_many(&ct, r, index, &candidates, rng);
+        assert!(!pk.verify_encryption_of_one_of_many(&ct, &candidates, &proof));
+    }
+}
+
+#[test]
+fn test_refresh() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+
+    // a ciphertext with non-normalized points, e.g. after homomorphic additions
+    let ct = pk.encrypt(m, Fr::rand(rng)) + pk.encrypt(G1Affine::zero(), Fr::rand(rng));
+    let refreshed = pk.refresh(ct, rng);
+
+    assert_ne!(refreshed, ct);
+    assert_eq!(sk.decrypt(refreshed), m);
+    assert!(refreshed.0.z.is_one() && refreshed.1.z.is_one());
+}
+
+#[test]
+fn test_encrypted_vec() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let table = BsgsTable::new(1000, 1 << 20).unwrap();
+    let encrypt = |values: &[u64], rng: &mut rand::rngs::ThreadRng| {
+        values
+            .iter()
+            .map(|&v| pk.encrypt_scalar(v, Fr::rand(rng)))
+            .collect::<EncryptedVec<G1>>()
+    };
+    let decrypt = |v: &EncryptedVec<G1>| {
+        v.as_slice()
+            .iter()
+            .map(|&ct| sk.decrypt_scalar(ct, &table).unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let x = encrypt(&[10, 20, 300], rng);
+    let y = encrypt(&[4, 5, 6], rng);
+    assert_eq!(decrypt(&x.add(&y).unwrap()), vec![14, 25, 306]);
+    assert_eq!(decrypt(&x.sub(&y).unwrap()), vec![6, 15, 294]);
+    assert_eq!(decrypt(&x.scalar_mul(Fr::from(3u64))), vec![30, 60, 900]);
+
+    let weights = [Fr::from(2u64), Fr::from(1u64), Fr::from(0u64)];
+    let dot = x.dot_plaintext(&weights).unwrap();
+    assert_eq!(sk.decrypt_scalar(dot, &table), Ok(40));
+
+    let short = encrypt(&[1, 2], rng);
+    assert_eq!(x.add(&short), Err(Error::LengthMismatch));
+    assert_eq!(x.sub(&short), Err(Error::LengthMismatch));
+    assert_eq!(x.dot_plaintext(&weights[..2]), Err(Error::LengthMismatch));
+}
+
+#[test]
+fn test_linear_combination() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    // evaluate the encrypted polynomial with random coefficients m_i at z
+    let z = Fr::rand(rng);
+    let coefficients: Vec<G1Affine> = (0..5).map(|_| G1Affine::rand(rng)).collect();
+    let terms: Vec<(Fr, Ciphertext<G1>)> = coefficients
+        .iter()
+        .enumerate()
+        .map(|(i, &m)| (z.pow([i as u64]), pk.encrypt(m, Fr::rand(rng))))
+        .collect();
+    let expected: G1 = terms
+        .iter()
+        .map(|(w, ct)| G1::from(sk.decrypt(*ct)) * w)
+        .sum();
+
+    let ct = Ciphertext::linear_combination(&terms);
+    assert_eq!(sk.decrypt(ct), G1Affine::from(expected));
+    assert_eq!(
+        Ciphertext::<G1>::linear_combination(&[]),
+        Ciphertext(G1::zero(), G1::zero())
+    );
+}
+
+#[test]
+fn test_serde_unexpected_curve() {
+    let rng = &mut rand::thread_rng();
+
+    // values over G2 cannot be read as values over G1
+    let ct = Ciphertext(G2::rand(rng), G2::rand(rng));
+    let err =
+        bincode::deserialize::<Ciphertext<G1>>(&bincode::serialize(&ct).unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), Error::UnexpectedCurve.to_string());
+
+    let key = DecryptKey::<G2>::new(G2Affine::rand(rng), Fr::rand(rng));
+    let bytes = bincode::serialize(key.encrypt_key()).unwrap();
+    assert!(bincode::deserialize::<PublicKey>(&bytes).is_err());
+    let bytes = bincode::serialize(&key).unwrap();
+    assert!(bincode::deserialize::<SecretKey>(&bytes).is_err());
+
+    // truncated bytes are rejected rather than panicking
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let ct = sk.public_key().encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let bytes = bincode::serialize(&ct).unwrap();
+    for len in [0, 2, 20, 99] {
+        let mut truncated = (len as u64).to_le_bytes().to_vec();
+        truncated.extend_from_slice(&bytes[8..8 + len]);
+        assert!(bincode::deserialize::<Ciphertext<G1>>(&truncated).is_err());
+    }
+}
+
+#[test]
+fn test_combine_keys() {
+    let rng = &mut rand::thread_rng();
+    let g1 = G1Affine::rand(rng);
+    let sk1 = SecretKey::new(g1, Fr::rand(rng));
+    let sk2 = SecretKey::new(g1, Fr::rand(rng));
+
+    let pk = PublicKey::combine(&[sk1.public_key(), sk2.public_key()]).unwrap();
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+
+    // neither secret alone decrypts, but their sum does
+    assert_ne!(sk1.decrypt(ct), m);
+    assert_ne!(sk2.decrypt(ct), m);
+    let sk = SecretKey::combine_secret(&[sk1, sk2]).unwrap();
+    assert!(sk.public_key() == pk);
+    assert_eq!(sk.decrypt(ct), m);
+
+    // the aggregate does not depend on the order of the keys
+    let sk3 = SecretKey::new(g1, Fr::rand(rng));
+    let mut keys = [sk1, sk2, sk3].map(|sk| sk.public_key());
+    let aggregate = PublicKey::combine(&keys).unwrap();
+    keys.reverse();
+    assert!(PublicKey::combine(&keys).unwrap() == aggregate);
+
+    // keys sort canonically by their bytes
+    let mut sorted = keys;
+    sorted.sort();
+    keys.sort_by_key(|k| k.to_bytes());
+    assert!(sorted == keys);
+    assert_eq!(sorted[0].cmp(&sorted[0]), std::cmp::Ordering::Equal);
+    assert!(sorted[0] < sorted[1] && sorted[1] < sorted[2]);
+
+    let other = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    assert!(sk1.public_key().same_generator(&sk2.public_key()));
+    assert!(!sk1.public_key().same_generator(&other.public_key()));
+    assert!(PublicKey::combine(&[sk1.public_key(), other.public_key()])
+        .is_err_and(|e| e == Error::GeneratorMismatch));
+    assert!(SecretKey::combine_secret(&[sk1, other]).is_err_and(|e| e == Error::GeneratorMismatch));
+    assert!(PublicKey::combine(&[]).is_err_and(|e| e == Error::EmptyInput));
+    assert!(SecretKey::combine_secret(&[]).is_err_and(|e| e == Error::EmptyInput));
+}
+
+#[test]
+fn test_decrypt_sigma_proof() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+
+    let proof = sk.prove_decrypt_sigma(&ct, rng);
+    assert!(pk.verify_decrypt_sigma(&ct, m, &proof));
+
+    // a challenge and a response
+    let mut bytes = Vec::new();
+    proof.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 64);
+    let decoded = SigmaProof::deserialize_compressed(&bytes[..]).unwrap();
+    assert!(pk.verify_decrypt_sigma(&ct, m, &decoded));
+
+    // the proof is bound to the message, the ciphertext and the key
+    assert!(!pk.verify_decrypt_sigma(&ct, G1Affine::rand(rng), &proof));
+    assert!(!pk.verify_decrypt_sigma(&pk.rerandomize(ct, Fr::rand(rng)), m, &proof));
+    let other = SecretKey::new(pk.generator(), Fr::rand(rng)).public_key();
+    assert!(!other.verify_decrypt_sigma(&ct, m, &proof));
+}
+
+#[test]
+fn test_decryption_batch_proof() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let ms = (0..8).map(|_| G1Affine::rand(rng)).collect::<Vec<_>>();
+    let cts = ms
+        .iter()
+        .map(|m| pk.encrypt(*m, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+
+    let proof = sk.prove_decryption_batch(&cts, rng);
+    assert!(pk.verify_decryption_batch(&cts, &ms, &proof));
+
+    // a challenge and a response, whatever the number of ciphertexts
+    let mut bytes = Vec::new();
+    proof.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 64);
+    let decoded = BatchDecryptionProof::deserialize_compressed(&bytes[..]).unwrap();
+    assert!(pk.verify_decryption_batch(&cts, &ms, &decoded));
+
+    // a single wrong message, swapped messages or a changed ciphertext fail
+    let mut wrong = ms.clone();
+    wrong[3] = G1Affine::rand(rng);
+    assert!(!pk.verify_decryption_batch(&cts, &wrong, &proof));
+    let mut swapped = ms.clone();
+    swapped.swap(0, 1);
+    assert!(!pk.verify_decryption_batch(&cts, &swapped, &proof));
+    let mut other_cts = cts.clone();
+    other_cts[5] = pk.rerandomize(cts[5], Fr::rand(rng));
+    assert!(!pk.verify_decryption_batch(&other_cts, &ms, &proof));
+    let other = SecretKey::new(pk.generator(), Fr::rand(rng)).public_key();
+    assert!(!other.verify_decryption_batch(&cts, &ms, &proof));
+
+    // mismatched lengths and empty batches
+    assert!(!pk.verify_decryption_batch(&cts, &ms[1..], &proof));
+    let empty = sk.prove_decryption_batch(&[], rng);
+    assert!(!pk.verify_decryption_batch(&[], &[], &empty));
+}
+
+#[test]
+fn test_zero_proof() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let (token, r, proof) = pk.encrypt_zero_proved(rng);
+    assert_eq!(token, pk.encrypt(G1Affine::zero(), r));
+    assert!(pk.verify_zero(&token, &proof));
+
+    // the token rerandomizes a ciphertext without changing its message
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+    assert_eq!(sk.decrypt(ct + token), m);
+
+    let mut bytes = Vec::new();
+    proof.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 64);
+    let decoded = ZeroProof::deserialize_compressed(&bytes[..]).unwrap();
+    assert!(pk.verify_zero(&token, &decoded));
+
+    // a token that shifts the message, or one under another key, is rejected
+    let sneaky = token + pk.encrypt(G1Affine::rand(rng), Fr::zero());
+    assert!(!pk.verify_zero(&sneaky, &proof));
+    let (_, _, other_proof) = pk.encrypt_zero_proved(rng);
+    assert!(!pk.verify_zero(&token, &other_proof));
+    let other = SecretKey::new(pk.generator(), Fr::rand(rng)).public_key();
+    assert!(!other.verify_zero(&token, &proof));
+}
+
+#[test]
+fn test_proof_digest() {
+    use sha2::{Sha256, Sha512};
+
+    let rng = &mut rand::thread_rng();
+    let dk = DecryptKey::<G1>::new(G1Affine::rand(rng), Fr::rand(rng));
+    let ek = dk.encrypt_key();
+    let (m, r) = (G1Affine::rand(rng), Fr::rand(rng));
+    let ct = ek.encrypt(m, r);
+
+    // a proof verifies only with the hash function it was created with
+    let proof = dk.prove_decrypt_sigma::<Sha512, _>(&ct, rng);
+    assert!(ek.verify_decrypt_sigma::<Sha512>(&ct, m, &proof));
+    assert!(!ek.verify_decrypt_sigma::<Sha256>(&ct, m, &proof));
+
+    let candidates = [G1Affine::rand(rng), m];
+    let proof = ek.prove_encryption_of_one_of::<Sha512, _>(&ct, r, true, candidates, rng);
+    assert!(ek.verify_encryption_of_one_of::<Sha512>(&ct, candidates, &proof));
+    assert!(!ek.verify_encryption_of_one_of::<Sha256>(&ct, candidates, &proof));
+
+    let proof = ek.prove_encryption_of_one_of_many::<Sha512, _>(&ct, r, 1, &candidates, rng);
+    assert!(ek.verify_encryption_of_one_of_many::<Sha512>(&ct, &candidates, &proof));
+    assert!(!ek.verify_encryption_of_one_of_many::<Sha256>(&ct, &candidates, &proof));
+}
+
+#[test]
+fn test_generators() {
+    assert_eq!(generators::G1_STANDARD, G1Affine::generator());
+    let sk = SecretKey::from_scalar(Fr::from(7u64));
+    assert_eq!(sk.public_key().generator(), generators::G1_STANDARD);
+
+    let h = generators::nums(b"pedersen-h");
+    assert_eq!(h, PublicKey::nums_generator(b"pedersen-h"));
+    assert_eq!(h, PublicKey::nums_generator(b"pedersen-h"));
+    assert_ne!(h, PublicKey::nums_generator(b"pedersen-h2"));
+    assert!(!h.is_zero() && h != G1Affine::generator());
+    assert!(h.is_on_curve() && h.is_in_correct_subgroup_assuming_on_curve());
+
+    // usable as the generator of a key
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::try_new(h, Fr::rand(rng)).unwrap();
+    let m = G1Affine::rand(rng);
+    assert_eq!(sk.decrypt(sk.public_key().encrypt(m, Fr::rand(rng))), m);
+}
+
+#[test]
+fn test_encrypt_with_label() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+
+    let ct = pk.encrypt_with_label(m, b"ballot/2026", Fr::rand(rng));
+    assert_eq!(sk.decrypt_with_label(ct, b"ballot/2026"), m);
+    assert_ne!(sk.decrypt(ct), m);
+    assert_eq!(
+        G1::from(sk.decrypt(ct)),
+        m + generators::label_point(b"ballot/2026")
+    );
+    assert_ne!(generators::label_point(b"x"), generators::nums(b"x"));
+
+    // in another context a structured message no longer decodes
+    let table = BsgsTable::new(100, 1 << 10).unwrap();
+    let m = (generators::G1_STANDARD * Fr::from(42u64)).into();
+    let ct = pk.encrypt_with_label(m, b"a", Fr::rand(rng));
+    assert_eq!(
+        sk.decrypt_scalar_with_label(ct, b"b", &table),
+        Err(Error::OutOfRange)
+    );
+    assert_eq!(sk.decrypt_scalar_with_label(ct, b"a", &table), Ok(42));
+
+    // but labels are public, so anyone can move a ciphertext to another label
+    let shift = generators::label_point(b"b") - generators::label_point(b"a");
+    let moved = Ciphertext(ct.0, ct.1 + shift);
+    assert_eq!(sk.decrypt_scalar_with_label(moved, b"b", &table), Ok(42));
+}
+
+#[test]
+fn test_encrypt_decrypt_signed() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    let table = BsgsTable::new(1000, 1 << 20).unwrap();
+    for v in [0, 1, -1, 999, -1000, 1000] {
+        let ct = pk.encrypt_signed(v, Fr::rand(rng));
+        assert_eq!(sk.decrypt_signed(ct, &table), Ok(v));
+    }
+    for v in [1001, -1001] {
+        let ct = pk.encrypt_signed(v, Fr::rand(rng));
+        assert_eq!(sk.decrypt_signed(ct, &table), Err(Error::OutOfRange));
+    }
+
+    // signed and unsigned encodings agree on non-negative values
+    let ct = pk.encrypt_signed(30, Fr::rand(rng)) + pk.encrypt_scalar(12, Fr::rand(rng));
+    assert_eq!(sk.decrypt_scalar(ct, &table), Ok(42));
+    let ct = ct + pk.encrypt_signed(-50, Fr::rand(rng));
+    assert_eq!(sk.decrypt_signed(ct, &table), Ok(-8));
+}
+
+#[test]
+fn test_secret_key_from_scalar() {
+    let rng = &mut rand::thread_rng();
+    let x = Fr::rand(rng);
+    let sk = SecretKey::from_scalar(x);
+    assert!(sk == SecretKey::new(G1Affine::generator(), x));
+    assert_eq!(sk.public_key().generator(), G1Affine::generator());
+    assert_eq!(sk.public_key().y(), G1Affine::generator() * x);
+}
+
+#[test]
+fn test_ciphertext_read_write_many() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+
+    // more than one chunk of records
+    let step = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    let cts = std::iter::successors(Some(step), |ct| Some(ct + step))
+        .take(5000)
+        .collect::<Vec<_>>();
+
+    let mut bytes = Vec::new();
+    Ciphertext::write_many(&mut bytes, &cts).unwrap();
+    assert_eq!(bytes.len(), cts.len() * 96);
+    assert_eq!(
+        Ciphertext::<G1>::read_many(&bytes[..], cts.len()).unwrap(),
+        cts
+    );
+    assert_eq!(
+        Ciphertext::<G1>::read_many(&bytes[..], 10).unwrap(),
+        cts[..10]
+    );
+
+    // truncated input and invalid points are rejected
+    let err = Ciphertext::<G1>::read_many(&bytes[..95], 1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    bytes[1] ^= 0xff;
+    let err = Ciphertext::<G1>::read_many(&bytes[..], 1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_cmp_ciphertexts() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let table = BsgsTable::new(1000, 1 << 20).unwrap();
+
+    let mut cts = [300, 7, 1000, 7, 0]
+        .map(|v| pk.encrypt_scalar(v, Fr::rand(rng)))
+        .to_vec();
+    cts.sort_by(|&a, &b| sk.cmp_ciphertexts(a, b, &table).unwrap());
+    let sorted = cts
+        .iter()
+        .map(|&ct| sk.decrypt_scalar(ct, &table).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(sorted, vec![0, 7, 7, 300, 1000]);
+
+    let out_of_range = pk.encrypt_scalar(1001, Fr::rand(rng));
+    assert_eq!(sk.cmp_ciphertexts(cts[0], out_of_range, &table), None);
+    assert_eq!(sk.cmp_ciphertexts(out_of_range, cts[0], &table), None);
+}
+
+#[test]
+fn test_encrypt_with_compressed() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let (mut g_bytes, mut y_bytes) = (Vec::new(), Vec::new());
+    pk.generator().serialize_compressed(&mut g_bytes).unwrap();
+    pk.y().serialize_compressed(&mut y_bytes).unwrap();
+
+    assert!(PublicKey::from_compressed(&g_bytes, &y_bytes).unwrap() == pk);
+    let (m, r) = (G1Affine::rand(rng), Fr::rand(rng));
+    let ct = PublicKey::encrypt_with_compressed(&g_bytes, &y_bytes, m, r).unwrap();
+    assert_eq!(ct, pk.encrypt(m, r));
+    assert_eq!(sk.decrypt(ct), m);
+
+    let mut identity = Vec::new();
+    G1Affine::zero()
+        .serialize_compressed(&mut identity)
+        .unwrap();
+    assert!(PublicKey::from_compressed(&identity, &y_bytes)
+        .is_err_and(|e| e == Error::InvalidGenerator));
+    assert!(PublicKey::from_compressed(&g_bytes, &y_bytes[..47])
+        .is_err_and(|e| e == Error::InvalidEncoding));
+    assert!(
+        PublicKey::from_compressed(&g_bytes, &[y_bytes.clone(), vec![0]].concat())
+            .is_err_and(|e| e == Error::InvalidEncoding)
+    );
+    y_bytes[47] ^= 1;
+    assert_eq!(
+        PublicKey::encrypt_with_compressed(&g_bytes, &y_bytes, m, r).err(),
+        Some(Error::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_encrypt_from_randomness_bytes() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+
+    let mut bytes = [0u8; util::RANDOM_BYTES_SIZE];
+    rand::RngCore::fill_bytes(rng, &mut bytes);
+    let (ct, r) = pk.encrypt_from_randomness_bytes(m, &bytes).unwrap();
+    assert_eq!(r, Fr::from_le_bytes_mod_order(&bytes));
+    assert_eq!(ct, pk.encrypt(m, r));
+    assert_eq!(sk.decrypt(ct), m);
+
+    // a 2^256 multiple wraps modulo the field order
+    let mut high = [0u8; util::RANDOM_BYTES_SIZE];
+    high[32] = 1;
+    let (_, r) = pk.encrypt_from_randomness_bytes(m, &high).unwrap();
+    assert_eq!(r, Fr::from(2u64).pow([256]));
+
+    assert_eq!(
+        pk.encrypt_from_randomness_bytes(m, &bytes[..32]).err(),
+        Some(Error::InvalidRandomness)
+    );
+
+    // the generic key takes the same constant-time path
+    let dk = DecryptKey::<G1>::new(G1Affine::rand(rng), Fr::rand(rng));
+    let ek = dk.encrypt_key();
+    let (ct, r) = ek.encrypt_from_randomness_bytes(m, &bytes).unwrap();
+    assert_eq!(r, util::reduce_bytes_ct(&bytes));
+    assert_eq!(dk.decrypt(ct), m);
+    assert_eq!(
+        ek.rerandomize_from_bytes(ct, &bytes[1..]),
+        Err(Error::InvalidRandomness)
+    );
+}
+
+#[test]
+fn test_rerandomize_from_bytes() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+
+    let mut bytes = [0u8; util::RANDOM_BYTES_SIZE];
+    rand::RngCore::fill_bytes(rng, &mut bytes);
+    let new_ct = pk.rerandomize_from_bytes(ct, &bytes).unwrap();
+    assert_ne!(new_ct, ct);
+    assert_eq!(sk.decrypt(new_ct), m);
+
+    // the same bytes give the same rerandomization, reduced as for encryption
+    assert_eq!(pk.rerandomize_from_bytes(ct, &bytes).unwrap(), new_ct);
+    let (_, r) = pk.encrypt_from_randomness_bytes(m, &bytes).unwrap();
+    assert_eq!(new_ct, pk.rerandomize(ct, r));
+    assert_eq!(new_ct, sk.rerandomize(ct, r));
+
+    assert_eq!(
+        pk.rerandomize_from_bytes(ct, &[bytes.as_slice(), &[0]].concat())
+            .err(),
+        Some(Error::InvalidRandomness)
+    );
+}
+
+#[test]
+fn test_degenerate_ciphertext() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let m = G1Affine::rand(rng);
+
+    let ct = pk.encrypt(m, Fr::zero());
+    assert!(ct.is_degenerate());
+    // the plaintext leaks as b
+    assert_eq!(ct.1, m);
+
+    let ct = pk.encrypt(m, Fr::rand(rng));
+    assert!(!ct.is_degenerate());
+    assert!((ct - ct).is_degenerate());
+
+    // the default ciphertext is the identity of the homomorphic addition
+    let zero = Ciphertext::default();
+    assert!(zero.is_degenerate());
+    assert!(sk.decrypt(zero).is_zero());
+    assert_eq!(ct + zero, ct);
+    assert_eq!([ct, ct].into_iter().fold(zero, |acc, ct| acc + ct), ct + ct);
+}
+
+#[test]
+fn test_multi_ciphertext() {
+    let rng = &mut rand::thread_rng();
+    let g1 = G1Affine::rand(rng);
+    let sks = (0..3)
+        .map(|_| SecretKey::new(g1, Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(SecretKey::public_key).collect::<Vec<_>>();
+    let (m, r) = (G1Affine::rand(rng), Fr::rand(rng));
+
+    let multi = PublicKey::encrypt_multi(&pks, m, r).unwrap();
+    assert_eq!(multi.len(), 3);
+    for (i, (sk, ct)) in sks.iter().zip(&multi).enumerate() {
+        assert_eq!(ct.0, multi.shared_a());
+        assert_eq!(*ct, pks[i].encrypt(m, r));
+        assert_eq!(sk.decrypt(multi[i]), m);
+    }
+    assert!(multi.get(3).is_none());
+    assert_eq!(multi.clone().into_iter().collect::<Vec<_>>().len(), 3);
+
+    // rebuilt from its parts
+    let bs = multi.iter().map(|ct| ct.1).collect::<Vec<_>>();
+    assert_eq!(
+        MultiCiphertext::from_parts(multi.shared_a(), bs),
+        Ok(multi.clone())
+    );
+    assert_eq!(
+        MultiCiphertext::from_parts(multi.shared_a(), Vec::new()),
+        Err(Error::EmptyInput)
+    );
+
+    let other = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    assert_eq!(
+        PublicKey::encrypt_multi(&[pks[0], other], m, r).err(),
+        Some(Error::GeneratorMismatch)
+    );
+    assert_eq!(
+        PublicKey::encrypt_multi(&[], m, r).err(),
+        Some(Error::EmptyInput)
+    );
+}
+
+#[test]
+#[should_panic(expected = "recipient index 1 is out of 1 recipients")]
+fn test_multi_ciphertext_index_out_of_bounds() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    let multi = PublicKey::encrypt_multi(&[pk], G1Affine::rand(rng), Fr::rand(rng)).unwrap();
+    let _ = multi[1];
+}
+
+#[test]
+fn test_reduce_bytes_ct() {
+    let rng = &mut rand::thread_rng();
+    let mut bytes = [0u8; 64];
+    for _ in 0..10 {
+        rand::RngCore::fill_bytes(rng, &mut bytes);
+        assert_eq!(
+            util::reduce_bytes_ct(&bytes),
+            Fr::from_le_bytes_mod_order(&bytes)
+        );
+        assert_eq!(
+            util::reduce_bytes_ct(&bytes[..32]),
+            Fr::from_le_bytes_mod_order(&bytes[..32])
+        );
+    }
+    assert_eq!(util::reduce_bytes_ct(&[]), Fr::zero());
+    assert_eq!(
+        util::reduce_bytes_ct(&[0xff; 64]),
+        Fr::from_le_bytes_mod_order(&[0xff; 64])
+    );
+    let modulus = Fr::MODULUS.to_bytes_le();
+    assert_eq!(util::reduce_bytes_ct(&modulus), Fr::zero());
+}
+
+#[test]
+fn test_decrypt_in_set() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let set = (0..3)
+        .map(|_| G1Affine::rand(rng))
+        .collect::<std::collections::HashSet<_>>();
+
+    for &m in &set {
+        // a ciphertext computed homomorphically is still found
+        let ct = pk.encrypt(m, Fr::rand(rng)) + pk.encrypt(G1Affine::zero(), Fr::rand(rng));
+        assert!(sk.decrypt_in_set(ct, &set));
+    }
+    let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+    assert!(!sk.decrypt_in_set(ct, &set));
+}
+
+#[test]
+fn test_same_secret_proof() {
+    let rng = &mut rand::thread_rng();
+    let (g1, g2) = (G1Affine::rand(rng), G1Affine::rand(rng));
+    let x = Fr::rand(rng);
+    let pk1 = SecretKey::new(g1, x).public_key();
+    let pk2 = SecretKey::new(g2, x).public_key();
+
+    let proof = SecretKey::new(g1, x).prove_same_secret(g2, rng);
+    assert!(pk1.verify_same_secret(&pk2, &proof));
+
+    let mut bytes = Vec::new();
+    proof.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 64);
+    let decoded = SameSecretProof::deserialize_compressed(&bytes[..]).unwrap();
+    assert!(pk1.verify_same_secret(&pk2, &decoded));
+
+    // the keys are bound in their order, and a different secret fails
+    assert!(!pk2.verify_same_secret(&pk1, &proof));
+    let other = SecretKey::new(g2, Fr::rand(rng)).public_key();
+    assert!(!pk1.verify_same_secret(&other, &proof));
+    let proof = SecretKey::new(g1, Fr::rand(rng)).prove_same_secret(g2, rng);
+    assert!(!pk1.verify_same_secret(&pk2, &proof));
+}
+
+#[test]
+fn test_ciphertext_mul_bits() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let m = G1Affine::rand(rng);
+    let ct = sk.public_key().encrypt(m, Fr::rand(rng));
+
+    // 13 = 0b1101, most significant bit first, with leading zeros
+    let bits = [false, false, true, true, false, true];
+    assert_eq!(ct.mul_bits(&bits), ct * Fr::from(13u64));
+    assert_eq!(sk.decrypt(ct.mul_bits(&bits)), m * Fr::from(13u64));
+    assert_eq!(ct.mul_bits(&[]), Ciphertext::default());
+
+    // a full-width scalar
+    let k = Fr::rand(rng);
+    let bits = k.into_bigint().to_bits_be();
+    assert_eq!(ct.mul_bits(&bits), ct * k);
+}
+
+#[test]
+fn test_ciphertext_commitment() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    let params = PedersenParams::standard();
+    let m = G1Affine::rand(rng);
+    let ct = pk.encrypt(m, Fr::rand(rng));
+    let s = Fr::rand(rng);
+
+    // opening recomputes the commitment, regardless of the projective form
+    let c = ct.commit(s, &params);
+    assert_eq!(
+        Ciphertext::try_from(&ct.to_bytes()[..])
+            .unwrap()
+            .commit(s, &params),
+        c
+    );
+    assert_ne!(ct.commit(Fr::rand(rng), &params), c);
+
+    // other ciphertexts, even of the same message, give other commitments
+    let others = [
+        pk.rerandomize(ct, Fr::rand(rng)),
+        pk.encrypt(m, Fr::rand(rng)),
+        Ciphertext(ct.1, ct.0),
+        Ciphertext::default(),
+    ];
+    for other in others {
+        assert_ne!(other.commit(s, &params), c);
+    }
+}
+
+#[test]
+fn test_match_commitment_proof() {
+    let rng = &mut rand::thread_rng();
+    let pk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng)).public_key();
+    let params = PedersenParams::standard();
+    let (v, r_enc, r_com) = (Fr::rand(rng), Fr::rand(rng), Fr::rand(rng));
+
+    let commitment = params.commit(v, r_com);
+    assert!(params.open(commitment, v, r_com));
+    let ct = pk.encrypt((params.g() * v).into(), r_enc);
+
+    let proof = pk.prove_encryption_matches_commitment(commitment, &ct, v, r_enc, r_com, rng);
+    assert!(pk.verify_encryption_matches_commitment(commitment, &ct, &proof));
+
+    let mut bytes = Vec::new();
+    proof.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 4 * 32);
+    let decoded = MatchProof::deserialize_compressed(&bytes[..]).unwrap();
+    assert!(pk.verify_encryption_matches_commitment(commitment, &ct, &decoded));
+
+    // a commitment or a ciphertext of another value fails
+    let other = params.commit(v + Fr::one(), r_com);
+    let proof = pk.prove_encryption_matches_commitment(other, &ct, v, r_enc, r_com, rng);
+    assert!(!pk.verify_encryption_matches_commitment(other, &ct, &proof));
+    let other_ct = pk.encrypt((params.g() * (v + Fr::one())).into(), r_enc);
+    let proof = pk.prove_encryption_matches_commitment(commitment, &other_ct, v, r_enc, r_com, rng);
+    assert!(!pk.verify_encryption_matches_commitment(commitment, &other_ct, &proof));
+}
+
+#[test]
+fn test_try_from_bytes() {
+    let rng = &mut rand::thread_rng();
+    let sk = SecretKey::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = sk.public_key();
+    let ct = pk.encrypt(G1Affine::rand(rng), Fr::rand(rng));
+
+    // exact length
+    let ct_bytes = ct.to_bytes();
+    assert_eq!(ct_bytes.len(), Ciphertext::SERIALIZED_SIZE);
+    assert_eq!(Ciphertext::try_from(&ct_bytes[..]), Ok(ct));
+    assert_eq!(Ciphertext::try_from(ct_bytes.to_vec()), Ok(ct));
+    let pk_bytes = pk.to_bytes();
+    assert!(PublicKey::try_from(&pk_bytes[..]).is_ok_and(|k| k == pk));
+    let sk_bytes = sk.to_bytes();
+    assert_eq!(sk_bytes.len(), SecretKey::SERIALIZED_SIZE);
+    assert!(SecretKey::try_from(sk_bytes.to_vec()).is_ok_and(|k| k == sk));
+
+    // too short and too long
+    let invalid = |expected, got| Some(Error::InvalidLength { expected, got });
+    assert_eq!(Ciphertext::try_from(&ct_bytes[..95]).err(), invalid(96, 95));
+    assert_eq!(
+        Ciphertext::try_from([&ct_bytes[..], &[0]].concat()).err(),
+        invalid(96, 97)
+    );
+    assert_eq!(PublicKey::try_from(&pk_bytes[..48]).err(), invalid(96, 48));
+    assert_eq!(PublicKey::try_from(&[][..]).err(), invalid(96, 0));
+    let long = [&sk_bytes[..], &[0]].concat();
+    assert_eq!(SecretKey::try_from(long).err(), invalid(128, 129));
+
+    // a public key that does not match the secret
+    let mut mismatched = sk_bytes;
+    mismatched[32..].copy_from_slice(
+        &SecretKey::new(G1Affine::rand(rng), Fr::rand(rng))
+            .public_key()
+            .to_bytes(),
+    );
+    assert_eq!(
+        SecretKey::try_from(&mismatched[..]).err(),
+        Some(Error::CorruptKey)
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "tokio")]
+
+use std::time::Duration;
+
+use ark_std::UniformRand;
+use bls_elgamal::{DecryptKey, Fr, G1Affine, G1};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn test_decrypt_service() {
+    let rng = &mut rand::thread_rng();
+    let sk = DecryptKey::<G1>::new(G1Affine::rand(rng), Fr::rand(rng));
+    let pk = *sk.encrypt_key();
+    let ms: Vec<_> = (0..25).map(|_| G1Affine::rand(rng)).collect();
+
+    let (ct_tx, ct_rx) = mpsc::channel(8);
+    let (m_tx, mut m_rx) = mpsc::channel(8);
+    let service = tokio::spawn(async move {
+        sk.decrypt_service(ct_rx, m_tx, 10, Duration::from_millis(5))
+            .await
+    });
+
+    // a partial batch is flushed after the timeout, while the channel is still open
+    for &m in &ms[..3] {
+        ct_tx.send(pk.encrypt(m, Fr::rand(rng))).await.unwrap();
+    }
+    for &m in &ms[..3] {
+        assert_eq!(m_rx.recv().await, Some(m));
+    }
+
+    // the rest are decrypted in order, and the service stops when the input is closed
+    let cts: Vec<_> = ms[3..]
+        .iter()
+        .map(|&m| pk.encrypt(m, Fr::rand(rng)))
+        .collect();
+    let sender = tokio::spawn(async move {
+        for ct in cts {
+            ct_tx.send(ct).await.unwrap();
+        }
+    });
+    for &m in &ms[3..] {
+        assert_eq!(m_rx.recv().await, Some(m));
+    }
+    sender.await.unwrap();
+    service.await.unwrap();
+    assert_eq!(m_rx.recv().await, None);
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "wasm")]
+
+use bls_elgamal::wasm;
+use rand::RngCore;
+
+fn random() -> Vec<u8> {
+    let mut bytes = vec![0u8; wasm::RANDOMNESS_SIZE];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_wasm_bindings() {
+    let sk = wasm::keygen(&random()).unwrap();
+    let pk = wasm::public_key(&sk).unwrap();
+
+    let yes = wasm::scalar_message(1);
+    let ct1 = wasm::encrypt(&pk, &yes, &random()).unwrap();
+    let ct2 = wasm::encrypt(&pk, &yes, &random()).unwrap();
+    assert_eq!(wasm::decrypt(&sk, &ct1).unwrap(), yes);
+
+    let tally = wasm::add(&ct1, &ct2).unwrap();
+    assert_eq!(wasm::decrypt(&sk, &tally).unwrap(), wasm::scalar_message(2));
+}